@@ -1,6 +1,8 @@
-use std::{collections::HashMap, ops::Index};
+use std::collections::HashMap;
 
-advent_of_code::solution!(5);
+advent_of_code::solution!(5, parse);
+
+type Partition = (Vec<Vec<u32>>, Vec<Vec<u32>>);
 
 fn parse_rules(input: &str) -> HashMap<u32, Vec<u32>> {
     let rules: HashMap<u32, Vec<u32>> = input.lines().fold(HashMap::new(), |mut acc, rule| {
@@ -35,7 +37,7 @@ fn parse_updates(input: &str) -> Vec<Vec<u32>> {
     ).collect()
 }
 
-fn divide_updates_by_validity<'a>(rules: &'a HashMap<u32, Vec<u32>>) -> impl FnMut((Vec<Vec<u32>>, Vec<Vec<u32>>), &'a Vec<u32>) -> (Vec<Vec<u32>>, Vec<Vec<u32>>) + 'a {
+fn divide_updates_by_validity<'a>(rules: &'a HashMap<u32, Vec<u32>>) -> impl FnMut(Partition, &'a Vec<u32>) -> Partition + 'a {
     |mut acc, update| {
         let mut is_valid = true;
 
@@ -72,28 +74,35 @@ fn divide_updates_by_validity<'a>(rules: &'a HashMap<u32, Vec<u32>>) -> impl FnM
     }
 }
 
-fn sum_middle_values(acc: u32, update: &Vec<u32>) -> u32 {
+fn sum_middle_values(acc: u32, update: &[u32]) -> u32 {
     acc + update[update.len() / 2]
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub struct Manual {
+    rules: HashMap<u32, Vec<u32>>,
+    updates: Vec<Vec<u32>>,
+}
+
+pub fn parse(input: &str) -> Manual {
     let rows = input.lines().collect::<Vec<&str>>();
     let mut rows_split = rows.split(|row| row == &"");
 
     let rules = parse_rules(rows_split.next().unwrap().join("\n").as_str());
     let updates = parse_updates(rows_split.next().unwrap().join("\n").as_str());
 
-    updates.iter().fold((vec![], vec![]), divide_updates_by_validity(&rules)).0.iter().fold(0, sum_middle_values).into()
+    Manual { rules, updates }
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let rows = input.lines().collect::<Vec<&str>>();
-    let mut rows_split = rows.split(|row| row == &"");
+pub fn part_one(manual: &Manual) -> Option<u32> {
+    let Manual { rules, updates } = manual;
 
-    let rules = parse_rules(rows_split.next().unwrap().join("\n").as_str());
-    let updates = parse_updates(rows_split.next().unwrap().join("\n").as_str());
+    updates.iter().fold((vec![], vec![]), divide_updates_by_validity(rules)).0.iter().fold(0, |acc, update| sum_middle_values(acc, update)).into()
+}
+
+pub fn part_two(manual: &Manual) -> Option<u32> {
+    let Manual { rules, updates } = manual;
 
-    let invalid_updates = updates.iter().fold((vec![], vec![]), divide_updates_by_validity(&rules)).1;
+    let invalid_updates = updates.iter().fold((vec![], vec![]), divide_updates_by_validity(rules)).1;
 
     let fixed_updates = invalid_updates.iter().map(|update| {
         let mut is_fixed = false;
@@ -121,7 +130,7 @@ pub fn part_two(input: &str) -> Option<u32> {
         fixed_update
     }).collect::<Vec<Vec<u32>>>();
 
-    fixed_updates.iter().fold(0, sum_middle_values).into()
+    fixed_updates.iter().fold(0, |acc, update| sum_middle_values(acc, update)).into()
 }
 
 #[cfg(test)]
@@ -130,13 +139,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse(&advent_of_code::template::read_file("examples", DAY)));
         assert_eq!(result, Some(143));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse(&advent_of_code::template::read_file("examples", DAY)));
         assert_eq!(result, Some(123));
     }
 }
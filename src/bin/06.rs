@@ -1,15 +1,15 @@
-use std::{fmt::Debug, thread::sleep, time::Duration};
+use std::{fmt::{Debug, Display}, thread::sleep, time::Duration};
 use colored::Colorize;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
-advent_of_code::solution!(6);
+advent_of_code::solution!(6, parse);
 
 fn clear_screen() {
     print!("\x1B[2J\x1B[1;1H");
 }
 
 #[derive(Debug, Clone, Copy)]
-struct Coord(i32, i32);
+pub struct Coord(i32, i32);
 
 impl PartialEq for Coord {
     fn eq(&self, other: &Self) -> bool {
@@ -44,19 +44,18 @@ impl Debug for Entity {
 
 impl PartialEq for Entity {
     fn eq(&self, other: &Self) -> bool {
-        match (self, other) {
-            (Entity::Wall, Entity::Wall) => true,
-            (Entity::Empty, Entity::Empty) => true,
-            _ => false,
-        }
+        matches!(
+            (self, other),
+            (Entity::Wall, Entity::Wall) | (Entity::Empty, Entity::Empty)
+        )
     }
 }
 
-impl Entity {
-    fn to_string(&self) -> String {
+impl Display for Entity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Entity::Wall => "#".to_string(),
-            Entity::Empty => ".".to_string(),
+            Entity::Wall => write!(f, "#"),
+            Entity::Empty => write!(f, "."),
         }
     }
 }
@@ -75,7 +74,7 @@ impl Debug for Tile {
 }
 
 #[derive(Debug, Clone)]
-struct Tiles(Vec<Vec<Tile>>);
+pub struct Tiles(Vec<Vec<Tile>>);
 
 impl Tiles {
     fn get(&self, coord: &Coord) -> &Tile {
@@ -87,7 +86,7 @@ impl Tiles {
     }
 
     fn get_empty_coords(&self) -> Vec<Coord> {
-        self.0.iter().flatten().filter(|tile| tile.entity == Entity::Empty).map(|tile| tile.coord.clone()).collect()
+        self.0.iter().flatten().filter(|tile| tile.entity == Entity::Empty).map(|tile| tile.coord).collect()
     }
 
     fn set_tile(&mut self, coord: &Coord, entity: Entity) {
@@ -110,7 +109,7 @@ impl Tiles {
         let mut guard_coord = guard_starting_coord;
         let mut guard_direction = Direction::Up;
 
-        let mut visited: Vec<(Coord, Direction)> = vec![(guard_coord, guard_direction.clone())];
+        let mut visited: Vec<(Coord, Direction)> = vec![(guard_coord, guard_direction)];
 
         while self.is_in_bounds(&guard_coord.add(&guard_direction.to_coord())) {
             let next_coord = guard_coord.add(&guard_direction.to_coord());
@@ -126,7 +125,7 @@ impl Tiles {
                 },
                 Entity::Empty => {
                     guard_coord = next_coord;
-                    visited.push((guard_coord, guard_direction.clone()));
+                    visited.push((guard_coord, guard_direction));
                     self.set_visited(&guard_coord);
                 },
             }
@@ -147,7 +146,7 @@ impl Tiles {
                 if tile.visited {
                     print!("{}", tile.entity.to_string().red());
                 } else {
-                    print!("{}", tile.entity.to_string());
+                    print!("{}", tile.entity);
                 }
             }
             println!();
@@ -164,7 +163,7 @@ enum Direction {
 }
 
 impl Direction {
-    fn to_coord(&self) -> Coord {
+    fn to_coord(self) -> Coord {
         match self {
             Direction::Up => Coord(0, -1),
             Direction::Down => Coord(0, 1),
@@ -184,10 +183,10 @@ impl Direction {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn parse(input: &str) -> (Tiles, Coord) {
     let mut guard_coord = Coord::new(0, 0);
 
-    let mut tiles: Tiles = Tiles(input
+    let tiles: Tiles = Tiles(input
         .lines()
         .enumerate()
         .map(|(y, line)| {
@@ -204,53 +203,28 @@ pub fn part_one(input: &str) -> Option<u32> {
                         },
                         _ => panic!("Invalid character"),
                     },
-                    visited: match c {
-                        '^' => true,
-                        _ => false,
-                    },
+                    visited: c == '^',
                 })
                 .collect()
         })
         .collect::<Vec<Vec<Tile>>>());
 
-    tiles.solve(guard_coord, false);
-
-    tiles.count_visited().into()
+    (tiles, guard_coord)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let mut guard_coord = Coord::new(0, 0);
+pub fn part_one((tiles, guard_coord): &(Tiles, Coord)) -> Option<u32> {
+    let mut tiles = tiles.clone();
 
-    let tiles: Tiles = Tiles(input
-        .lines()
-        .enumerate()
-        .map(|(y, line)| {
-            line.chars()
-                .enumerate()
-                .map(|(x, c)| Tile {
-                    coord: Coord::new(x as i32, y as i32),
-                    entity: match c {
-                        '#' => Entity::Wall,
-                        '.' => Entity::Empty,
-                        '^' => {
-                            guard_coord = Coord::new(x as i32, y as i32);
-                            Entity::Empty
-                        },
-                        _ => panic!("Invalid character"),
-                    },
-                    visited: match c {
-                        '^' => true,
-                        _ => false,
-                    },
-                })
-                .collect()
-        })
-        .collect::<Vec<Vec<Tile>>>());
+    tiles.solve(*guard_coord, false);
 
+    tiles.count_visited().into()
+}
+
+pub fn part_two((tiles, guard_coord): &(Tiles, Coord)) -> Option<u32> {
     let solution = tiles.get_empty_coords().par_iter().filter(|coord| {
         let mut simulated_tiles = tiles.clone();
         simulated_tiles.set_tile(coord, Entity::Wall);
-        !simulated_tiles.solve(guard_coord, false)
+        !simulated_tiles.solve(*guard_coord, false)
     }).count();
 
     Some(solution as u32)
@@ -260,15 +234,5 @@ pub fn part_two(input: &str) -> Option<u32> {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(41));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(6));
-    }
+    advent_of_code::example_tests!(parse);
 }
@@ -1,6 +1,4 @@
-use itertools::Itertools;
-
-advent_of_code::solution!(7);
+advent_of_code::solution!(7, parse);
 
 enum Operation {
     Add,
@@ -18,8 +16,8 @@ impl Operation {
     }
 }
 
-fn solve(operations: &Vec<Operation>, acc: u64, expected: u64, numbers: Vec<u64>) -> bool {
-    if numbers.len() == 0 {
+fn solve(operations: &[Operation], acc: u64, expected: u64, numbers: &[u64]) -> bool {
+    if numbers.is_empty() {
         return acc == expected;
     }
 
@@ -30,44 +28,40 @@ fn solve(operations: &Vec<Operation>, acc: u64, expected: u64, numbers: Vec<u64>
     let next_number = numbers.first().unwrap();
 
     operations.iter().any(|operation| {
-        solve(operations, operation.apply(acc, *next_number), expected, numbers[1..].to_vec())
+        solve(operations, operation.apply(acc, *next_number), expected, &numbers[1..])
     })
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let operations: &Vec<Operation> = &vec![Operation::Add, Operation::Multiply];
-
-    input.lines().filter_map(|line| {
-        if let Some((expected, rest)) = line.split_once(":") {
-            Some((expected.parse::<u64>().ok().unwrap(), rest.trim().split(" ").map(|n| n.parse::<u64>().ok().unwrap()).collect::<Vec<u64>>()))
-        } else {
-            None
-        }
-    })
+fn sum_solvable(operations: &[Operation], equations: &[(u64, Vec<u64>)]) -> u64 {
+    equations.iter()
     .filter_map(|(expected, numbers)| {
-        match solve(operations, 0, expected, numbers) {
+        match solve(operations, 0, *expected, numbers) {
             true => Some(expected),
             false => None
         }
-    }).sum::<u64>().into()
+    }).sum::<u64>()
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let operations: &Vec<Operation> = &vec![Operation::Add, Operation::Multiply, Operation::Concat];
-
+pub fn parse(input: &str) -> Vec<(u64, Vec<u64>)> {
     input.lines().filter_map(|line| {
         if let Some((expected, rest)) = line.split_once(":") {
             Some((expected.parse::<u64>().ok().unwrap(), rest.trim().split(" ").map(|n| n.parse::<u64>().ok().unwrap()).collect::<Vec<u64>>()))
         } else {
             None
         }
-    })
-    .filter_map(|(expected, numbers)| {
-        match solve(operations, 0, expected, numbers) {
-            true => Some(expected),
-            false => None
-        }
-    }).sum::<u64>().into()
+    }).collect()
+}
+
+pub fn part_one(equations: &[(u64, Vec<u64>)]) -> Option<u64> {
+    let operations = [Operation::Add, Operation::Multiply];
+
+    sum_solvable(&operations, equations).into()
+}
+
+pub fn part_two(equations: &[(u64, Vec<u64>)]) -> Option<u64> {
+    let operations = [Operation::Add, Operation::Multiply, Operation::Concat];
+
+    sum_solvable(&operations, equations).into()
 }
 
 #[cfg(test)]
@@ -76,13 +70,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse(&advent_of_code::template::read_file("examples", DAY)));
         assert_eq!(result, Some(3749));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse(&advent_of_code::template::read_file("examples", DAY)));
         assert_eq!(result, Some(11387));
     }
 }
@@ -2,14 +2,14 @@ use std::{collections::{HashMap}, hash::Hash};
 
 use itertools::Itertools;
 
-advent_of_code::solution!(8);
+advent_of_code::solution!(8, parse);
 
 fn is_out_of_bound(grid_end: Coord, pos: Coord) -> bool {
     pos.0 < 0 || pos.1 < 0 || pos.0 > grid_end.0 || pos.1 > grid_end.1
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
-struct Coord(i32, i32);
+pub struct Coord(i32, i32);
 
 impl Coord {
     fn new(x: i32, y: i32) -> Self {
@@ -25,7 +25,12 @@ impl Coord {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub struct Map {
+    antennas: HashMap<char, Vec<Coord>>,
+    grid_end: Coord,
+}
+
+pub fn parse(input: &str) -> Map {
     let mut antennas: HashMap<char, Vec<Coord>> = HashMap::new();
 
     let grid = input.lines().map(|line| line.chars().collect::<Vec<char>>()).collect::<Vec<Vec<char>>>();
@@ -49,19 +54,24 @@ pub fn part_one(input: &str) -> Option<u32> {
         });
     });
 
+    Map { antennas, grid_end }
+}
+
+pub fn part_one(map: &Map) -> Option<u32> {
+    let Map { antennas, grid_end } = map;
+
     let antinodes = antennas
     .iter()
     .filter(|(_, coords)| coords.len() > 1)
-    .map(|(_, antenna_coords)| {
-        antenna_coords.iter().tuple_combinations().map(|(first, second)| {
+    .flat_map(|(_, antenna_coords)| {
+        antenna_coords.iter().tuple_combinations().flat_map(|(first, second)| {
             let diff_vector = first.sub(second);
 
             vec![first.add(&diff_vector), second.sub(&diff_vector)]
-        }).flatten().collect::<Vec<Coord>>()
+        }).collect::<Vec<Coord>>()
     })
-    .flatten()
     .filter(|antinode| {
-        !is_out_of_bound(grid_end, *antinode)
+        !is_out_of_bound(*grid_end, *antinode)
     })
     .unique()
     .collect::<Vec<Coord>>();
@@ -69,40 +79,19 @@ pub fn part_one(input: &str) -> Option<u32> {
     (antinodes.len() as u32).into()
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let mut antennas: HashMap<char, Vec<Coord>> = HashMap::new();
-
-    let grid = input.lines().map(|line| line.chars().collect::<Vec<char>>()).collect::<Vec<Vec<char>>>();
-    let grid_end = Coord::new((grid[0].len() as i32)-1, (grid.len() as i32)-1);
-
-    input.lines().enumerate().for_each(|(y, line)| {
-        line.chars().enumerate().for_each(|(x, char)| {
-            match char {
-                '.' => {},
-                _ => {
-                    let mut new_values = vec![Coord::new(x as i32, y as i32)];
-
-                    if antennas.contains_key(&char) {
-                        let old_values = antennas.get(&char).unwrap();
-                        new_values = old_values.iter().cloned().chain(new_values.iter().cloned()).collect();
-                    }
-
-                    antennas.insert(char, new_values);
-                }
-            }
-        });
-    });
+pub fn part_two(map: &Map) -> Option<u32> {
+    let Map { antennas, grid_end } = map;
 
     let antinodes = antennas
     .iter()
     .filter(|(_, coords)| coords.len() > 1)
-    .map(|(_, antenna_coords)| {
-        antenna_coords.iter().tuple_combinations().map(|(first, second)| {
+    .flat_map(|(_, antenna_coords)| {
+        antenna_coords.iter().tuple_combinations().flat_map(|(first, second)| {
             let diff_vector = first.sub(second);
 
             let mut first_antennas = vec![];
             let mut current_coord = *first;
-            while !is_out_of_bound(grid_end, current_coord) {
+            while !is_out_of_bound(*grid_end, current_coord) {
                 first_antennas.push(current_coord);
 
                 current_coord = current_coord.add(&diff_vector);
@@ -110,18 +99,17 @@ pub fn part_two(input: &str) -> Option<u32> {
 
             let mut second_antennas = vec![];
             let mut current_coord = *second;
-            while !is_out_of_bound(grid_end, current_coord) {
+            while !is_out_of_bound(*grid_end, current_coord) {
                 second_antennas.push(current_coord);
 
                 current_coord = current_coord.sub(&diff_vector);
             }
 
             first_antennas.iter().cloned().chain(second_antennas.iter().cloned()).collect::<Vec<Coord>>()
-        }).flatten().collect::<Vec<Coord>>()
+        }).collect::<Vec<Coord>>()
     })
-    .flatten()
     .filter(|antinode| {
-        !is_out_of_bound(grid_end, *antinode)
+        !is_out_of_bound(*grid_end, *antinode)
     })
     .unique()
     .collect::<Vec<Coord>>();
@@ -135,13 +123,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse(&advent_of_code::template::read_file("examples", DAY)));
         assert_eq!(result, Some(14));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse(&advent_of_code::template::read_file("examples", DAY)));
         assert_eq!(result, Some(34));
     }
 }
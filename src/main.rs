@@ -1,4 +1,7 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::time::Comparison;
+use advent_of_code::template::commands::{
+    all, download, extract, leaderboard, read, scaffold, session, solve, stars, time, verify,
+};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
@@ -7,72 +10,162 @@ use advent_of_code::template::Day;
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::{input::InputSource, runner::RunOptions, Day, DayRange};
+    use pico_args::Arguments;
     use std::process;
 
     pub enum AppArguments {
         Download {
             day: Day,
+            force: bool,
+        },
+        DownloadAll {
+            days: DayRange,
+            year: Option<u16>,
+            scaffold: bool,
+            force: bool,
         },
         Read {
             day: Day,
         },
+        Extract {
+            day: Day,
+            overwrite: bool,
+        },
+        Leaderboard {
+            day: Option<Day>,
+            id: u64,
+        },
+        Stars {
+            leaderboard: bool,
+        },
+        Session {
+            account: Option<String>,
+            refresh: bool,
+        },
         Scaffold {
             day: Day,
             download: bool,
             overwrite: bool,
+            force: bool,
         },
         Solve {
             day: Day,
             release: bool,
             dhat: bool,
+            memory: bool,
             submit: Option<u8>,
+            input: InputSource,
+            download: bool,
+            options: RunOptions,
         },
         All {
             release: bool,
+            memory: bool,
+            options: RunOptions,
         },
         Time {
             all: bool,
             day: Option<Day>,
             store: bool,
+            memory: bool,
+            options: RunOptions,
+            compare: bool,
+            fail_on_regression: Option<f64>,
+        },
+        Verify {
+            options: RunOptions,
         },
         #[cfg(feature = "today")]
         Today,
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
-        let mut args = pico_args::Arguments::from_env();
+        let mut args = Arguments::from_env();
 
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
+                memory: args.contains("--memory"),
+                options: RunOptions::from_args(&mut args)?,
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let memory = args.contains("--memory");
+                let options = RunOptions {
+                    time: true,
+                    ..RunOptions::from_args(&mut args)?
+                };
+                let compare = args.contains("--compare");
+                let fail_on_regression = args.opt_value_from_str("--fail-on-regression")?;
 
                 AppArguments::Time {
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    memory,
+                    options,
+                    compare,
+                    fail_on_regression,
                 }
             }
-            Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+            Some("verify") => AppArguments::Verify {
+                options: RunOptions::from_args(&mut args)?,
             },
+            Some("download") => {
+                let all = args.contains("--all");
+                let range: Option<DayRange> = args.opt_value_from_str("--range")?;
+                let year = args.opt_value_from_str("--year")?;
+                let force = args.contains("--force");
+
+                if all || range.is_some() || year.is_some() {
+                    AppArguments::DownloadAll {
+                        days: range.unwrap_or_else(DayRange::all),
+                        year,
+                        scaffold: args.contains("--scaffold"),
+                        force,
+                    }
+                } else {
+                    AppArguments::Download {
+                        day: args.free_from_str()?,
+                        force,
+                    }
+                }
+            }
             Some("read") => AppArguments::Read {
                 day: args.free_from_str()?,
             },
+            Some("leaderboard") => AppArguments::Leaderboard {
+                day: args.opt_value_from_str("--day")?,
+                id: args.free_from_str()?,
+            },
+            Some("stars") => AppArguments::Stars {
+                leaderboard: args.contains("--leaderboard"),
+            },
+            Some("session") => AppArguments::Session {
+                account: args.opt_value_from_str("--account")?,
+                refresh: args.contains("--refresh"),
+            },
+            Some("extract") => AppArguments::Extract {
+                day: args.free_from_str()?,
+                overwrite: args.contains("--overwrite"),
+            },
             Some("scaffold") => AppArguments::Scaffold {
                 day: args.free_from_str()?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
+                force: args.contains("--force"),
             },
             Some("solve") => AppArguments::Solve {
                 day: args.free_from_str()?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                memory: args.contains("--memory"),
+                input: InputSource::from_args(&mut args)?,
+                download: args.contains("--download"),
+                options: RunOptions::from_args(&mut args)?,
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
@@ -102,29 +195,70 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
+            AppArguments::All {
+                release,
+                memory,
+                options,
+            } => all::handle(release, memory, &options),
+            AppArguments::Time {
+                day,
+                all,
+                store,
+                memory,
+                options,
+                compare,
+                fail_on_regression,
+            } => {
+                // failing on regressions implies comparing with the stored timings.
+                let comparison = (compare || fail_on_regression.is_some())
+                    .then_some(Comparison { fail_on_regression });
+                time::handle(day, all, store, memory, &options, comparison.as_ref());
+            }
+            AppArguments::Verify { options } => verify::handle(&options),
+            AppArguments::Download { day, force } => download::handle(day, force),
+            AppArguments::DownloadAll {
+                days,
+                year,
+                scaffold,
+                force,
+            } => download::handle_all(days, year, scaffold, force),
             AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day, download, overwrite } => {
+            AppArguments::Extract { day, overwrite } => extract::handle(day, overwrite),
+            AppArguments::Leaderboard { day, id } => leaderboard::handle(id, day),
+            AppArguments::Stars { leaderboard } => stars::handle(leaderboard),
+            AppArguments::Session { account, refresh } => session::handle(account, refresh),
+            AppArguments::Scaffold {
+                day,
+                download,
+                overwrite,
+                force,
+            } => {
                 scaffold::handle(day, overwrite);
                 if download {
-                    download::handle(day);
+                    download::handle(day, force);
+                    extract::handle(day, false);
                 }
             }
             AppArguments::Solve {
                 day,
                 release,
                 dhat,
+                memory,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                input,
+                download,
+                options,
+            } => solve::handle(
+                day, release, dhat, memory, submit, &input, download, &options,
+            ),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
                     Some(day) => {
                         scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
+                        download::handle(day, false);
+                        extract::handle(day, false);
+                        read::print_puzzle(day);
                     }
                     None => {
                         eprintln!(
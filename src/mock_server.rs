@@ -0,0 +1,69 @@
+use std::{net::TcpListener, path::PathBuf, process, time::Duration};
+
+use advent_of_code::template::backend::DEFAULT_FIXTURES_DIR;
+use advent_of_code::template::fixtures::Fixtures;
+use advent_of_code::template::mock_server::{MockServer, DEFAULT_COOLDOWN};
+use pico_args::Arguments;
+
+struct Options {
+    port: u16,
+    dir: PathBuf,
+    session: Option<String>,
+    cooldown: Duration,
+}
+
+fn parse() -> Result<Options, pico_args::Error> {
+    let mut args = Arguments::from_env();
+
+    let options = Options {
+        port: args.opt_value_from_str("--port")?.unwrap_or(3000),
+        dir: args
+            .opt_value_from_str("--dir")?
+            .unwrap_or_else(|| DEFAULT_FIXTURES_DIR.into()),
+        session: args.opt_value_from_str("--session")?,
+        cooldown: args
+            .opt_value_from_str("--cooldown")?
+            .map_or(DEFAULT_COOLDOWN, Duration::from_secs),
+    };
+
+    let remaining = args.finish();
+    if !remaining.is_empty() {
+        eprintln!("Warning: unknown argument(s): {remaining:?}.");
+    }
+
+    Ok(options)
+}
+
+fn main() {
+    let options = match parse() {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("Error: {err}");
+            process::exit(1);
+        }
+    };
+
+    let listener = match TcpListener::bind(("127.0.0.1", options.port)) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("Failed to listen on port {}: {e}", options.port);
+            process::exit(1);
+        }
+    };
+
+    println!(
+        "🎄 Serving \"{}\" on http://127.0.0.1:{}, set AOC_BASE_URL to this address to use it.",
+        options.dir.display(),
+        options.port
+    );
+
+    let mut server = MockServer::new(
+        Fixtures::new(options.dir),
+        options.session,
+        options.cooldown,
+    );
+    if let Err(e) = server.serve(&listener) {
+        eprintln!("{e}");
+        process::exit(1);
+    }
+}
@@ -0,0 +1,270 @@
+/// Stores the known answers of solved days, one file per year in `data/answers/<year>.json`.
+/// Answers are added when a submission is accepted, or by hand. `cargo verify` checks solutions against them.
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::examples::optional_answer;
+use crate::template::{year_from_env, Day};
+
+static ANSWERS_DIR: &str = "./data/answers";
+
+/// The known answers of a single day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DayAnswers {
+    pub day: Day,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl DayAnswers {
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// The known answers of all days, sorted by day.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub data: Vec<DayAnswers>,
+}
+
+impl Answers {
+    /// Returns the path of the answers of the year in `AOC_YEAR`, `data/answers/<year>.json`.
+    pub fn path() -> Result<PathBuf, String> {
+        year_from_env().map(|year| Path::new(ANSWERS_DIR).join(format!("{year}.json")))
+    }
+
+    /// Reads the answers of the year in `AOC_YEAR`. If not present, returns empty answers.
+    pub fn read_from_file() -> Result<Self, String> {
+        let path = Answers::path()?;
+
+        match fs::read_to_string(&path) {
+            Ok(content) => {
+                Answers::try_from(content).map_err(|e| format!("{}: {e}", path.display()))
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(format!("{}: {e}", path.display())),
+        }
+    }
+
+    /// Writes the answers of the year in `AOC_YEAR`.
+    pub fn store_file(&self) -> Result<(), String> {
+        let path = Answers::path()?;
+        let json = JsonValue::from(self);
+
+        fs::create_dir_all(ANSWERS_DIR)
+            .and_then(|()| fs::File::create(&path))
+            .and_then(|mut file| json.format_to(&mut file))
+            .map_err(|e| format!("{}: {e}", path.display()))
+    }
+
+    pub fn get(&self, day: Day, part: u8) -> Option<&str> {
+        self.data
+            .iter()
+            .find(|answers| answers.day == day)
+            .and_then(|answers| answers.get(part))
+    }
+
+    /// Sets the answer of a part, overwriting a previously known answer.
+    pub fn set(&mut self, day: Day, part: u8, answer: &str) {
+        let index = match self.data.binary_search_by_key(&day, |answers| answers.day) {
+            Ok(index) => index,
+            Err(index) => {
+                self.data.insert(
+                    index,
+                    DayAnswers {
+                        day,
+                        part_1: None,
+                        part_2: None,
+                    },
+                );
+                index
+            }
+        };
+
+        let answers = &mut self.data[index];
+        match part {
+            1 => answers.part_1 = Some(answer.into()),
+            2 => answers.part_2 = Some(answer.into()),
+            _ => {}
+        }
+    }
+}
+
+/// Records an accepted answer in the answers of the year in `AOC_YEAR` and returns the path of the answers.
+pub fn record_correct(day: Day, part: u8, answer: &str) -> Result<PathBuf, String> {
+    let mut answers = Answers::read_from_file()?;
+
+    if answers.get(day, part) != Some(answer) {
+        answers.set(day, part, answer);
+        answers.store_file()?;
+    }
+
+    Answers::path()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answers> for JsonValue {
+    fn from(value: &Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl From<&DayAnswers> for JsonValue {
+    fn from(value: &DayAnswers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+
+        for (key, answer) in [("part_1", &value.part_1), ("part_2", &value.part_2)] {
+            map.insert(
+                key.into(),
+                match answer {
+                    Some(x) => JsonValue::String(x.clone()),
+                    None => JsonValue::Null,
+                },
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        let mut data: Vec<DayAnswers> = json_data
+            .iter()
+            .map(DayAnswers::try_from)
+            .collect::<Result<_, _>>()?;
+
+        data.sort_unstable_by_key(|answers| answers.day);
+
+        Ok(Answers { data })
+    }
+}
+
+impl TryFrom<&JsonValue> for DayAnswers {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answers to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answers.day to be a Day struct.")?;
+
+        Ok(DayAnswers {
+            day,
+            part_1: optional_answer(json, "answers.part_1")?,
+            part_2: optional_answer(json, "answers.part_2")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{Answers, DayAnswers};
+    use crate::day;
+
+    #[test]
+    fn parses_answers() {
+        let answers = Answers::try_from(
+            r#"{ "data": [
+                { "day": "07", "part_1": "1620690235709", "part_2": null },
+                { "day": "01", "part_1": 11, "part_2": 31 }
+            ] }"#
+                .to_string(),
+        )
+        .unwrap();
+
+        assert_eq!(answers.data[0].day, day!(1));
+        assert_eq!(answers.get(day!(1), 2), Some("31"));
+        assert_eq!(answers.get(day!(7), 1), Some("1620690235709"));
+        assert_eq!(answers.get(day!(7), 2), None);
+        assert_eq!(answers.get(day!(2), 1), None);
+    }
+
+    #[test]
+    fn rejects_invalid_answers() {
+        assert!(Answers::try_from(r#"{ "data": [{ "part_1": 1 }] }"#.to_string()).is_err());
+        assert!(
+            Answers::try_from(r#"{ "data": [{ "day": "01", "part_1": [] }] }"#.to_string())
+                .is_err()
+        );
+        assert!(Answers::try_from("[]".to_string()).is_err());
+    }
+
+    #[test]
+    fn sets_answers() {
+        let mut answers = Answers::default();
+        answers.set(day!(5), 2, "123");
+        answers.set(day!(2), 1, "4");
+        answers.set(day!(5), 1, "7");
+        answers.set(day!(5), 2, "124");
+
+        assert_eq!(
+            answers.data,
+            vec![
+                DayAnswers {
+                    day: day!(2),
+                    part_1: Some("4".into()),
+                    part_2: None,
+                },
+                DayAnswers {
+                    day: day!(5),
+                    part_1: Some("7".into()),
+                    part_2: Some("124".into()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn roundtrips_answers() {
+        let mut answers = Answers::default();
+        answers.set(day!(3), 1, "161");
+        answers.set(day!(3), 2, "48");
+        answers.set(day!(12), 2, "line 1\nline 2");
+
+        let json = JsonValue::from(&answers).stringify().unwrap();
+        assert_eq!(Answers::try_from(json).unwrap(), answers);
+    }
+}
@@ -1,9 +1,14 @@
 /// Wrapper module around the "aoc-cli" command-line.
 use std::{
+    env,
     fmt::Display,
-    process::{Command, Output, Stdio},
+    fs,
+    process::{self, Command, Output, Stdio},
 };
 
+use crate::template::aoc_client::{AocClientError, Fetched, Puzzle, SubmitResponse, Validators};
+use crate::template::backend::AocBackend;
+use crate::template::session;
 use crate::template::Day;
 
 #[derive(Debug)]
@@ -33,59 +38,123 @@ pub fn check() -> Result<(), AocCommandError> {
     Ok(())
 }
 
-pub fn read(day: Day) -> Result<Output, AocCommandError> {
-    let puzzle_path = get_puzzle_path(day);
+/// A backend that shells out to aoc-cli, which has to be installed and configured separately.
+pub struct AocCli {
+    year: Option<u16>,
+    /// The session cookie of the named account in `AOC_ACCOUNT`. aoc-cli reads the default account's session itself.
+    session: Option<String>,
+}
 
-    let args = build_args(
-        "read",
-        &[
-            "--description-only".into(),
-            "--puzzle-file".into(),
-            puzzle_path,
-        ],
-        day,
-    );
+impl AocCli {
+    /// Checks that aoc-cli is installed. Requests go to the year in `AOC_YEAR`, or the current event if it is not set.
+    pub fn from_env() -> Result<Self, String> {
+        AocCli::for_year(get_year())
+    }
 
-    call_aoc_cli(&args)
-}
+    /// Like [`AocCli::from_env`], for the event in `year` instead of `AOC_YEAR`.
+    pub fn for_year(year: Option<u16>) -> Result<Self, String> {
+        check().map_err(|e| e.to_string())?;
 
-pub fn download(day: Day) -> Result<Output, AocCommandError> {
-    let input_path = get_input_path(day);
-    let puzzle_path = get_puzzle_path(day);
-
-    let args = build_args(
-        "download",
-        &[
-            "--overwrite".into(),
-            "--input-file".into(),
-            input_path.to_string(),
-            "--puzzle-file".into(),
-            puzzle_path.to_string(),
-        ],
-        day,
-    );
-
-    let output = call_aoc_cli(&args)?;
-    println!("---");
-    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
-    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
-    Ok(output)
-}
+        let session = match session::account_from_env()? {
+            Some(account) => Some(session::read(Some(&account)).ok_or_else(|| {
+                AocClientError::MissingSession(Some(account.clone())).to_string()
+            })?),
+            None => None,
+        };
 
-pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
-    // workaround: the argument order is inverted for submit.
-    let mut args = build_args("submit", &[], day);
-    args.push(part.to_string());
-    args.push(result.to_string());
-    call_aoc_cli(&args)
-}
+        Ok(AocCli { year, session })
+    }
+
+    fn command(&self) -> Command {
+        let mut command = Command::new("aoc");
+        if let Some(session) = &self.session {
+            command.env("ADVENT_OF_CODE_SESSION", session);
+        }
+        command
+    }
+
+    /// Runs a download command that writes to a temporary file, and returns the file's contents.
+    fn download_to_file(&self, day: Day, args: &[&str]) -> Result<String, AocClientError> {
+        let path = env::temp_dir().join(format!("aoc-cli-{day}-{}", process::id()));
+        let path_arg = path.to_string_lossy().to_string();
+
+        let mut cmd_args: Vec<String> = args.iter().map(ToString::to_string).collect();
+        cmd_args.push(path_arg);
+        cmd_args.push("--overwrite".into());
+
+        let result = call_aoc_cli(self.command(), &self.build_args("download", &cmd_args, day))
+            .map_err(|e| AocClientError::Backend(e.to_string()))
+            .and_then(|_| {
+                fs::read_to_string(&path).map_err(|e| AocClientError::Backend(e.to_string()))
+            });
 
-fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+        let _ = fs::remove_file(&path);
+        result
+    }
+
+    fn build_args(&self, command: &str, args: &[String], day: Day) -> Vec<String> {
+        build_args(command, args, day, self.year)
+    }
 }
 
-fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
+impl AocBackend for AocCli {
+    /// aoc-cli does not revalidate downloads, so the input is always downloaded again.
+    fn input(&self, day: Day, _: &Validators) -> Result<Fetched<String>, AocClientError> {
+        self.download_to_file(day, &["--input-only", "--input-file"])
+            .map(|input| Fetched::Modified(input, Validators::default()))
+    }
+
+    fn puzzle(&self, day: Day, _: &Validators) -> Result<Fetched<Puzzle>, AocClientError> {
+        let markdown = self.download_to_file(day, &["--puzzle-only", "--puzzle-file"])?;
+        // aoc-cli starts the description of each part with a `## ` heading.
+        let parts = markdown
+            .lines()
+            .filter(|line| line.starts_with("## "))
+            .count();
+
+        Ok(Fetched::Modified(
+            Puzzle {
+                markdown,
+                parts: u8::try_from(parts).unwrap_or(u8::MAX),
+            },
+            Validators::default(),
+        ))
+    }
+
+    fn leaderboard(&self, _: u64) -> Result<String, AocClientError> {
+        Err(AocClientError::Backend(
+            "aoc-cli cannot download the JSON of private leaderboards, use the native backend."
+                .into(),
+        ))
+    }
+
+    fn submit(&self, day: Day, part: u8, answer: &str) -> Result<SubmitResponse, AocClientError> {
+        // workaround: the argument order is inverted for submit.
+        let mut args = self.build_args("submit", &[], day);
+        args.push(part.to_string());
+        args.push(answer.to_string());
+
+        // capture the response to find out whether the answer was accepted.
+        let output = self
+            .command()
+            .args(&args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .output()
+            .map_err(|_| {
+                AocClientError::Backend(AocCommandError::CommandNotCallable.to_string())
+            })?;
+
+        if !output.status.success() {
+            return Err(AocClientError::Backend(
+                AocCommandError::BadExitStatus(output).to_string(),
+            ));
+        }
+
+        Ok(SubmitResponse::from_message(
+            String::from_utf8_lossy(&output.stdout).to_string(),
+        ))
+    }
 }
 
 fn get_year() -> Option<u16> {
@@ -95,10 +164,10 @@ fn get_year() -> Option<u16> {
     }
 }
 
-fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
+fn build_args(command: &str, args: &[String], day: Day, year: Option<u16>) -> Vec<String> {
     let mut cmd_args = args.to_vec();
 
-    if let Some(year) = get_year() {
+    if let Some(year) = year {
         cmd_args.push("--year".into());
         cmd_args.push(year.to_string());
     }
@@ -108,11 +177,11 @@ fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
     cmd_args
 }
 
-fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
+fn call_aoc_cli(mut command: Command, args: &[String]) -> Result<Output, AocCommandError> {
     // println!("Calling >aoc with: {}", args.join(" "));
-    let output = Command::new("aoc")
+    let output = command
         .args(args)
-        .stdout(Stdio::inherit())
+        .stdout(Stdio::null())
         .stderr(Stdio::inherit())
         .output()
         .map_err(|_| AocCommandError::CommandNotCallable)?;
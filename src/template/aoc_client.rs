@@ -0,0 +1,646 @@
+/// A small HTTP client for the Advent of Code website.
+/// Downloads inputs and puzzle descriptions and submits answers, authenticated with the session cookie.
+use std::{
+    env,
+    fmt::{self, Display},
+    time::Duration,
+};
+
+use ureq::{Agent, AgentBuilder};
+
+use crate::template::backend::AocBackend;
+use crate::template::markdown;
+use crate::template::session;
+use crate::template::submissions::Verdict;
+use crate::template::{year_from_env, Day};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Sent unless `AOC_USER_AGENT` is set. The Advent of Code maintainers ask automated tools to identify themselves.
+const DEFAULT_USER_AGENT: &str = "github.com/fspoettel/advent-of-code-rust";
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum AocClientError {
+    /// No session cookie for an account, `None` being the default account.
+    MissingSession(Option<String>),
+    /// The session cookie of an account does not look like one.
+    InvalidSession {
+        account: Option<String>,
+        reason: String,
+    },
+    /// The server does not accept the session cookie of an account, it expired or was revoked.
+    LoggedOut(Option<String>),
+    /// The day is not unlocked yet.
+    NotUnlocked,
+    /// `AOC_YEAR` is not set or not a number.
+    MissingYear,
+    /// The server responded with an error status.
+    Status { status: u16, body: String },
+    /// The request failed before the server responded.
+    Transport(String),
+    /// The puzzle page does not contain a puzzle description.
+    MissingDescription,
+    /// Another backend than the native client failed, e.g. aoc-cli.
+    Backend(String),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AocClientError::MissingSession(account) => write!(
+                f,
+                "No session cookie found for {}, set {}. {}",
+                session::describe(account.as_deref()),
+                session::env_var(account.as_deref()),
+                session::refresh_instructions(account.as_deref())
+            ),
+            AocClientError::InvalidSession { account, reason } => write!(
+                f,
+                "The session cookie of {} is invalid. {reason} {}",
+                session::describe(account.as_deref()),
+                session::refresh_instructions(account.as_deref())
+            ),
+            AocClientError::LoggedOut(account) => write!(
+                f,
+                "Advent of Code does not accept the session cookie of {}, it has expired or was revoked. {}",
+                session::describe(account.as_deref()),
+                session::refresh_instructions(account.as_deref())
+            ),
+            AocClientError::NotUnlocked => write!(f, "The day is not unlocked yet."),
+            AocClientError::MissingYear => {
+                write!(f, "AOC_YEAR is not set, set it in .cargo/config.toml.")
+            }
+            AocClientError::Status { status, .. } => {
+                write!(f, "The server responded with status {status}.")
+            }
+            AocClientError::Transport(e) => write!(f, "The request failed: {e}"),
+            AocClientError::MissingDescription => {
+                write!(f, "The puzzle page does not contain a description.")
+            }
+            AocClientError::Backend(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// What the server made of a submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SubmitOutcome {
+    Judged(Verdict),
+    /// An answer was submitted too recently. Contains the time left to wait, e.g. `34s`, if the server sent it.
+    TooRecent(Option<String>),
+    /// The part is solved already or not unlocked yet.
+    WrongLevel,
+    /// The response could not be understood.
+    Unknown,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SubmitResponse {
+    pub outcome: SubmitOutcome,
+    /// The server's message, converted to markdown.
+    pub message: String,
+}
+
+impl SubmitResponse {
+    pub fn from_html(html: &str) -> Self {
+        let message = markdown::articles(html)
+            .first()
+            .map_or_else(|| markdown::to_markdown(html), |a| markdown::to_markdown(a));
+
+        SubmitResponse::from_message(message)
+    }
+
+    /// Reads the outcome from a response that was converted to text already, e.g. by aoc-cli.
+    pub fn from_message(message: String) -> Self {
+        let outcome = if let Some(verdict) = Verdict::from_response(&message) {
+            SubmitOutcome::Judged(verdict)
+        } else if message.contains("You gave an answer too recently") {
+            SubmitOutcome::TooRecent(wait_time(&message))
+        } else if message.contains("You don't seem to be solving the right level") {
+            SubmitOutcome::WrongLevel
+        } else {
+            SubmitOutcome::Unknown
+        };
+
+        SubmitResponse { outcome, message }
+    }
+}
+
+/// Response headers that allow asking the server whether a resource changed since it was downloaded.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Validators {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+/// The result of a conditional request.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Fetched<T> {
+    Modified(T, Validators),
+    /// The resource did not change since the request's validators were received.
+    NotModified,
+}
+
+impl<T> Fetched<T> {
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Fetched<U> {
+        match self {
+            Fetched::Modified(value, validators) => Fetched::Modified(f(value), validators),
+            Fetched::NotModified => Fetched::NotModified,
+        }
+    }
+}
+
+/// A puzzle description, converted to markdown.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Puzzle {
+    pub markdown: String,
+    /// The number of unlocked parts.
+    pub parts: u8,
+}
+
+impl Puzzle {
+    /// Converts the `<article>`s of a puzzle page, one per unlocked part.
+    pub fn from_articles(articles: &[&str]) -> Result<Self, AocClientError> {
+        if articles.is_empty() {
+            return Err(AocClientError::MissingDescription);
+        }
+
+        let markdown = articles
+            .iter()
+            .map(|article| markdown::to_markdown(article))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        Ok(Puzzle {
+            markdown,
+            parts: u8::try_from(articles.len()).unwrap_or(u8::MAX),
+        })
+    }
+}
+
+/// Reads the time left from e.g. "You have 34s left to wait.".
+fn wait_time(message: &str) -> Option<String> {
+    let start = message.find("You have ")? + "You have ".len();
+    let end = message[start..].find(" left to wait")?;
+    Some(message[start..start + end].to_string())
+}
+
+pub struct AocClient {
+    agent: Agent,
+    base_url: String,
+    year: u16,
+    session: String,
+    /// The named account that the session belongs to, `None` for the default account.
+    account: Option<String>,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, year: u16, session: &str) -> Self {
+        AocClient::with_user_agent(base_url, year, session, DEFAULT_USER_AGENT)
+    }
+
+    pub fn with_user_agent(base_url: &str, year: u16, session: &str, user_agent: &str) -> Self {
+        AocClient {
+            agent: AgentBuilder::new()
+                .user_agent(user_agent)
+                .timeout(Duration::from_secs(30))
+                .build(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            year,
+            session: session::strip_name(session).to_string(),
+            account: None,
+        }
+    }
+
+    /// Configures the client from the environment: `AOC_YEAR`, the session cookie of the account in `AOC_ACCOUNT`, `AOC_BASE_URL`, which defaults to the Advent of Code website, and `AOC_USER_AGENT`.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        AocClient::for_year(year_from_env().ok())
+    }
+
+    /// Like [`AocClient::from_env`], for the event in `year` instead of `AOC_YEAR`.
+    pub fn for_year(year: Option<u16>) -> Result<Self, AocClientError> {
+        let account = session::account_from_env().map_err(AocClientError::Backend)?;
+        let session = read_session(account.as_deref())?;
+        AocClient::configure(account.as_deref(), &session, year)
+    }
+
+    /// Like [`AocClient::from_env`], with the session cookie of another account.
+    pub fn for_account(account: Option<&str>) -> Result<Self, AocClientError> {
+        let session = read_session(account)?;
+        AocClient::for_session(account, &session)
+    }
+
+    /// Like [`AocClient::from_env`], with a given session cookie of an account.
+    /// The session cookie is checked to look like one before it is used.
+    pub fn for_session(account: Option<&str>, session: &str) -> Result<Self, AocClientError> {
+        AocClient::configure(account, session, year_from_env().ok())
+    }
+
+    fn configure(
+        account: Option<&str>,
+        session: &str,
+        year: Option<u16>,
+    ) -> Result<Self, AocClientError> {
+        let year = year.ok_or(AocClientError::MissingYear)?;
+
+        session::check_format(session).map_err(|reason| AocClientError::InvalidSession {
+            account: account.map(Into::into),
+            reason,
+        })?;
+
+        let base_url = env::var("AOC_BASE_URL")
+            .ok()
+            .filter(|url| !url.is_empty())
+            .unwrap_or_else(|| DEFAULT_BASE_URL.into());
+
+        let user_agent = env::var("AOC_USER_AGENT")
+            .ok()
+            .filter(|agent| !agent.trim().is_empty())
+            .unwrap_or_else(|| DEFAULT_USER_AGENT.into());
+
+        Ok(AocClient {
+            account: account.map(Into::into),
+            ..AocClient::with_user_agent(&base_url, year, session, &user_agent)
+        })
+    }
+
+    /// Asks the server whether the session is logged in. Returns the name that the website shows for the user.
+    pub fn validate(&self) -> Result<String, AocClientError> {
+        let html = match self.get("", &Validators::default())? {
+            Fetched::Modified(html, _) => html,
+            Fetched::NotModified => String::new(),
+        };
+
+        let start = html
+            .find("<div class=\"user\">")
+            .map(|i| i + "<div class=\"user\">".len())
+            .ok_or_else(|| AocClientError::LoggedOut(self.account.clone()))?;
+        let end = html[start..].find('<').map_or(html.len(), |i| start + i);
+
+        Ok(markdown::to_markdown(&html[start..end]).trim().to_string())
+    }
+
+    pub fn input(
+        &self,
+        day: Day,
+        validators: &Validators,
+    ) -> Result<Fetched<String>, AocClientError> {
+        self.get(&format!("day/{}/input", day.into_inner()), validators)
+    }
+
+    /// Downloads the puzzle description of all unlocked parts.
+    pub fn puzzle(
+        &self,
+        day: Day,
+        validators: &Validators,
+    ) -> Result<Fetched<Puzzle>, AocClientError> {
+        let (html, validators) = match self.get(&format!("day/{}", day.into_inner()), validators)? {
+            Fetched::Modified(html, validators) => (html, validators),
+            Fetched::NotModified => return Ok(Fetched::NotModified),
+        };
+
+        let puzzle = Puzzle::from_articles(&markdown::articles(&html))?;
+        Ok(Fetched::Modified(puzzle, validators))
+    }
+
+    /// Downloads the JSON of a private leaderboard. The session has to belong to a member of the leaderboard.
+    pub fn leaderboard(&self, id: u64) -> Result<String, AocClientError> {
+        match self.get(
+            &format!("leaderboard/private/view/{id}.json"),
+            &Validators::default(),
+        )? {
+            Fetched::Modified(json, _) => Ok(json),
+            Fetched::NotModified => Err(AocClientError::Transport(
+                "unexpected response 304 Not Modified.".into(),
+            )),
+        }
+    }
+
+    pub fn submit(
+        &self,
+        day: Day,
+        part: u8,
+        answer: &str,
+    ) -> Result<SubmitResponse, AocClientError> {
+        let response = self
+            .agent
+            .post(&self.url(&format!("day/{}/answer", day.into_inner())))
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)]);
+
+        self.check_session(read_body(response))
+            .map(|html| SubmitResponse::from_html(&html))
+    }
+
+    fn get(&self, path: &str, validators: &Validators) -> Result<Fetched<String>, AocClientError> {
+        let mut request = self
+            .agent
+            .get(&self.url(path))
+            .set("Cookie", &self.cookie());
+
+        if let Some(etag) = &validators.etag {
+            request = request.set("If-None-Match", etag);
+        }
+        if let Some(last_modified) = &validators.last_modified {
+            request = request.set("If-Modified-Since", last_modified);
+        }
+
+        let response = match request.call() {
+            Ok(response) if response.status() == 304 => return Ok(Fetched::NotModified),
+            response => response,
+        };
+
+        let validators = match &response {
+            Ok(response) => Validators {
+                etag: response.header("ETag").map(Into::into),
+                last_modified: response.header("Last-Modified").map(Into::into),
+            },
+            Err(_) => Validators::default(),
+        };
+
+        self.check_session(read_body(response))
+            .map(|body| Fetched::Modified(body, validators))
+    }
+
+    /// Recognises the responses to a session that is not logged in, and to days that are not unlocked yet.
+    /// Without a valid session, inputs respond with a 400 and pages link to the login.
+    fn check_session(
+        &self,
+        body: Result<String, AocClientError>,
+    ) -> Result<String, AocClientError> {
+        match body {
+            Ok(body) if body.contains("/auth/login") => {
+                Err(AocClientError::LoggedOut(self.account.clone()))
+            }
+            Err(AocClientError::Status { status: 400, body }) if body.contains("log in") => {
+                Err(AocClientError::LoggedOut(self.account.clone()))
+            }
+            Err(AocClientError::Status { status: 404, body })
+                if body.contains("before it unlocks") =>
+            {
+                Err(AocClientError::NotUnlocked)
+            }
+            body => body,
+        }
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/{}/{path}", self.base_url, self.year)
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+}
+
+impl AocBackend for AocClient {
+    fn input(&self, day: Day, validators: &Validators) -> Result<Fetched<String>, AocClientError> {
+        AocClient::input(self, day, validators)
+    }
+
+    fn puzzle(&self, day: Day, validators: &Validators) -> Result<Fetched<Puzzle>, AocClientError> {
+        AocClient::puzzle(self, day, validators)
+    }
+
+    fn leaderboard(&self, id: u64) -> Result<String, AocClientError> {
+        AocClient::leaderboard(self, id)
+    }
+
+    fn submit(&self, day: Day, part: u8, answer: &str) -> Result<SubmitResponse, AocClientError> {
+        AocClient::submit(self, day, part, answer)
+    }
+}
+
+/// Reads the session cookie of an account, the default account if `None`.
+fn read_session(account: Option<&str>) -> Result<String, AocClientError> {
+    session::read(account).ok_or_else(|| AocClientError::MissingSession(account.map(Into::into)))
+}
+
+fn read_body(response: Result<ureq::Response, ureq::Error>) -> Result<String, AocClientError> {
+    match response {
+        Ok(response) => response
+            .into_string()
+            .map_err(|e| AocClientError::Transport(e.to_string())),
+        Err(ureq::Error::Status(status, response)) => Err(AocClientError::Status {
+            status,
+            body: response.into_string().unwrap_or_default(),
+        }),
+        Err(ureq::Error::Transport(e)) => Err(AocClientError::Transport(e.to_string())),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    use super::{
+        AocClient, AocClientError, Fetched, Puzzle, SubmitOutcome, SubmitResponse, Validators,
+    };
+    use crate::day;
+    use crate::template::submissions::Verdict;
+
+    /// Serves the given responses, one per connection, and returns the requests it received.
+    /// Responses are a status, extra header lines and a body.
+    fn serve(
+        responses: Vec<(u16, &'static str, &'static str)>,
+    ) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            responses
+                .into_iter()
+                .map(|(status, headers, body)| {
+                    let (stream, _) = listener.accept().unwrap();
+                    let mut reader = BufReader::new(stream);
+
+                    let mut request = String::new();
+                    let mut content_length = 0;
+                    loop {
+                        let mut line = String::new();
+                        reader.read_line(&mut line).unwrap();
+                        if let Some(length) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                            content_length = length.trim().parse().unwrap();
+                        }
+                        if line == "\r\n" {
+                            break;
+                        }
+                        request.push_str(&line);
+                    }
+
+                    let mut body_in = vec![0; content_length];
+                    reader.read_exact(&mut body_in).unwrap();
+                    request.push_str(&String::from_utf8(body_in).unwrap());
+
+                    write!(
+                        reader.get_mut(),
+                        "HTTP/1.1 {status} OK\r\n{headers}Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                        body.len()
+                    )
+                    .unwrap();
+
+                    request
+                })
+                .collect()
+        });
+
+        (base_url, handle)
+    }
+
+    #[test]
+    fn downloads_inputs() {
+        let (base_url, server) = serve(vec![
+            (200, "ETag: \"abc\"\r\n", "3   4\n4   3\n"),
+            (304, "", ""),
+        ]);
+        let client = AocClient::new(&format!("{base_url}/"), 2024, "abc\n");
+
+        let validators = Validators {
+            etag: Some("\"abc\"".into()),
+            last_modified: None,
+        };
+        assert_eq!(
+            client.input(day!(1), &Validators::default()).unwrap(),
+            Fetched::Modified("3   4\n4   3\n".into(), validators.clone())
+        );
+        assert_eq!(
+            client.input(day!(1), &validators).unwrap(),
+            Fetched::NotModified
+        );
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /2024/day/1/input HTTP/1.1"));
+        assert!(requests[0].contains("Cookie: session=abc\r\n"));
+        assert!(requests[1].contains("If-None-Match: \"abc\"\r\n"));
+    }
+
+    #[test]
+    fn downloads_puzzles() {
+        let (base_url, server) = serve(vec![
+            (
+                200,
+                "",
+                r#"<main><article class="day-desc"><h2>--- Day 2 ---</h2><p>Part <em>one</em>.</p></article>
+                <article class="day-desc"><h2 id="part2">--- Part Two ---</h2></article></main>"#,
+            ),
+            (200, "", "<main><p>Please log in.</p></main>"),
+        ]);
+        let client = AocClient::with_user_agent(&base_url, 2024, "abc", "me@example.com");
+
+        assert_eq!(
+            client.puzzle(day!(2), &Validators::default()).unwrap(),
+            Fetched::Modified(
+                Puzzle {
+                    markdown: "## --- Day 2 ---\n\nPart *one*.\n\n## --- Part Two ---\n".into(),
+                    parts: 2,
+                },
+                Validators::default()
+            )
+        );
+        assert_eq!(
+            client.puzzle(day!(2), &Validators::default()),
+            Err(AocClientError::MissingDescription)
+        );
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].contains("User-Agent: me@example.com\r\n"));
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (base_url, server) = serve(vec![(
+            200,
+            "",
+            "<main><article><p>That's not the right answer; your answer is too low. Please wait one minute.</p></article></main>",
+        )]);
+        let client = AocClient::new(&base_url, 2024, "abc");
+
+        let response = client.submit(day!(7), 2, "1 + 1").unwrap();
+        assert_eq!(response.outcome, SubmitOutcome::Judged(Verdict::TooLow));
+        assert!(response.message.starts_with("That's not the right answer"));
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2024/day/7/answer HTTP/1.1"));
+        assert!(requests[0].ends_with("level=2&answer=1+%2B+1"));
+    }
+
+    #[test]
+    fn downloads_leaderboards() {
+        let (base_url, server) = serve(vec![(200, "", r#"{ "members": {} }"#)]);
+        let client = AocClient::new(&base_url, 2024, "abc");
+
+        assert_eq!(client.leaderboard(12345).unwrap(), r#"{ "members": {} }"#);
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /2024/leaderboard/private/view/12345.json HTTP/1.1"));
+    }
+
+    #[test]
+    fn reports_error_statuses() {
+        let (base_url, server) = serve(vec![(404, "", "Not found")]);
+        let client = AocClient::new(&base_url, 2024, "abc");
+
+        assert_eq!(
+            client.input(day!(25), &Validators::default()),
+            Err(AocClientError::Status {
+                status: 404,
+                body: "Not found".into()
+            })
+        );
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn detects_expired_sessions() {
+        let (base_url, server) = serve(vec![
+            (400, "", "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"),
+            (200, "", "<header><a href=\"/2024/auth/login\">[Log In]</a></header><main><article><h2>--- Day 1 ---</h2></article></main>"),
+            (404, "", "Please don't repeatedly request this endpoint before it unlocks! The calendar countdown is synchronized with the server time; the link will be enabled on the calendar the instant this puzzle becomes available.\n"),
+            (200, "", "<header><div class=\"user\">Ada <span class=\"star-count\">4*</span></div></header>"),
+        ]);
+        let client = AocClient::new(&base_url, 2024, "abc");
+
+        assert_eq!(
+            client.input(day!(1), &Validators::default()),
+            Err(AocClientError::LoggedOut(None))
+        );
+        assert_eq!(
+            client.puzzle(day!(1), &Validators::default()),
+            Err(AocClientError::LoggedOut(None))
+        );
+        assert_eq!(
+            client.input(day!(25), &Validators::default()),
+            Err(AocClientError::NotUnlocked)
+        );
+        assert_eq!(client.validate(), Ok("Ada".into()));
+
+        let requests = server.join().unwrap();
+        assert!(requests[3].starts_with("GET /2024/ HTTP/1.1"));
+    }
+
+    #[test]
+    fn reads_submit_responses() {
+        let response = SubmitResponse::from_html(
+            "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 34s left to wait.</p></article>",
+        );
+        assert_eq!(
+            response.outcome,
+            SubmitOutcome::TooRecent(Some("34s".into()))
+        );
+
+        let response = SubmitResponse::from_html(
+            "<article><p>You don't seem to be solving the right level.  Did you already complete it?</p></article>",
+        );
+        assert_eq!(response.outcome, SubmitOutcome::WrongLevel);
+
+        let response = SubmitResponse::from_html("<article><p>Hm.</p></article>");
+        assert_eq!(response.outcome, SubmitOutcome::Unknown);
+        assert_eq!(response.message, "Hm.\n");
+    }
+}
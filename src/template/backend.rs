@@ -0,0 +1,123 @@
+/// The ways of talking to Advent of Code that the commands can use.
+/// `AOC_BACKEND` selects one of them: the native HTTP client by default, aoc-cli, or a fixtures directory for working offline.
+use std::{env, fmt, path::PathBuf, str::FromStr};
+
+use crate::template::aoc_cli::AocCli;
+use crate::template::aoc_client::{
+    AocClient, AocClientError, Fetched, Puzzle, SubmitResponse, Validators,
+};
+use crate::template::fixtures::Fixtures;
+use crate::template::{year_from_env, Day};
+
+/// Used by the fixtures backend unless `AOC_FIXTURES_DIR` is set.
+pub const DEFAULT_FIXTURES_DIR: &str = "data/fixtures";
+
+/// Downloads inputs, puzzle descriptions and leaderboards, and submits answers.
+pub trait AocBackend {
+    /// Downloads the input of a day, unless it did not change since `validators` were received.
+    fn input(&self, day: Day, validators: &Validators) -> Result<Fetched<String>, AocClientError>;
+
+    /// Downloads the puzzle description of all unlocked parts of a day.
+    fn puzzle(&self, day: Day, validators: &Validators) -> Result<Fetched<Puzzle>, AocClientError>;
+
+    /// Downloads the JSON of a private leaderboard.
+    fn leaderboard(&self, id: u64) -> Result<String, AocClientError>;
+
+    fn submit(&self, day: Day, part: u8, answer: &str) -> Result<SubmitResponse, AocClientError>;
+
+    /// Whether requests go to the Advent of Code servers, and have to be spaced out.
+    fn is_remote(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BackendKind {
+    #[default]
+    Native,
+    AocCli,
+    Fixtures,
+}
+
+impl BackendKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            BackendKind::Native => "native",
+            BackendKind::AocCli => "aoc-cli",
+            BackendKind::Fixtures => "fixtures",
+        }
+    }
+}
+
+impl fmt::Display for BackendKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for BackendKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "native" => Ok(BackendKind::Native),
+            "aoc-cli" => Ok(BackendKind::AocCli),
+            "fixtures" => Ok(BackendKind::Fixtures),
+            _ => Err(format!(
+                "Unknown backend `{s}`, expected `native`, `aoc-cli` or `fixtures`."
+            )),
+        }
+    }
+}
+
+/// Creates the backend selected by `AOC_BACKEND`, for the event in `AOC_YEAR`.
+pub fn from_env() -> Result<Box<dyn AocBackend>, String> {
+    for_year(year_from_env().ok())
+}
+
+/// Like [`from_env`], for the event in `year` instead of `AOC_YEAR`.
+/// Without a year, the native backend fails and aoc-cli requests the current event.
+pub fn for_year(year: Option<u16>) -> Result<Box<dyn AocBackend>, String> {
+    let kind = match env::var("AOC_BACKEND") {
+        Ok(kind) if !kind.trim().is_empty() => kind
+            .trim()
+            .parse()
+            .map_err(|e| format!("AOC_BACKEND: {e}"))?,
+        _ => BackendKind::default(),
+    };
+
+    match kind {
+        BackendKind::Native => AocClient::for_year(year)
+            .map(|client| Box::new(client) as Box<dyn AocBackend>)
+            .map_err(|e| e.to_string()),
+        BackendKind::AocCli => {
+            AocCli::for_year(year).map(|cli| Box::new(cli) as Box<dyn AocBackend>)
+        }
+        BackendKind::Fixtures => {
+            let dir = env::var("AOC_FIXTURES_DIR")
+                .ok()
+                .filter(|dir| !dir.trim().is_empty())
+                .unwrap_or_else(|| DEFAULT_FIXTURES_DIR.into());
+            Ok(Box::new(Fixtures::new(PathBuf::from(dir))))
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::BackendKind;
+
+    #[test]
+    fn parses_backend_kinds() {
+        for kind in [
+            BackendKind::Native,
+            BackendKind::AocCli,
+            BackendKind::Fixtures,
+        ] {
+            assert_eq!(kind.as_str().parse::<BackendKind>(), Ok(kind));
+        }
+        assert!("curl".parse::<BackendKind>().is_err());
+    }
+}
@@ -0,0 +1,285 @@
+/// Settings that control how often a solution is executed when benching.
+use std::{cmp, collections::HashMap, time::Duration};
+
+use pico_args::Arguments;
+use tinyjson::JsonValue;
+
+/// How long and how often a solution stage is benched.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BenchSettings {
+    /// Time budget for taking samples, the sample count is derived from the duration of the first execution.
+    pub time: Duration,
+    pub min_samples: u64,
+    pub max_samples: u64,
+    /// Number of discarded warmup iterations. Defaults to a tenth of the sample count.
+    pub warmup: Option<u64>,
+}
+
+impl Default for BenchSettings {
+    fn default() -> Self {
+        BenchSettings {
+            time: Duration::from_secs(1),
+            min_samples: 10,
+            max_samples: 10_000,
+            warmup: None,
+        }
+    }
+}
+
+impl BenchSettings {
+    /// Reads `--bench-time <seconds>`, `--min-samples <n>`, `--max-samples <n>` and `--warmup <n>`.
+    /// Options that are not present keep their default value.
+    pub fn from_args(args: &mut Arguments) -> Result<Self, pico_args::Error> {
+        let defaults = BenchSettings::default();
+
+        let settings = BenchSettings {
+            time: args
+                .opt_value_from_fn("--bench-time", parse_seconds)?
+                .unwrap_or(defaults.time),
+            min_samples: args
+                .opt_value_from_str("--min-samples")?
+                .unwrap_or(defaults.min_samples),
+            max_samples: args
+                .opt_value_from_str("--max-samples")?
+                .unwrap_or(defaults.max_samples),
+            warmup: args.opt_value_from_str("--warmup")?,
+        };
+
+        settings
+            .validate()
+            .map_err(|cause| pico_args::Error::ArgumentParsingFailed { cause })?;
+
+        Ok(settings)
+    }
+
+    /// Formats the settings as arguments for a solution binary, the inverse of [`BenchSettings::from_args`].
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![
+            "--bench-time".into(),
+            self.time.as_secs_f64().to_string(),
+            "--min-samples".into(),
+            self.min_samples.to_string(),
+            "--max-samples".into(),
+            self.max_samples.to_string(),
+        ];
+
+        if let Some(warmup) = self.warmup {
+            args.extend(["--warmup".into(), warmup.to_string()]);
+        }
+
+        args
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.min_samples == 0 {
+            return Err("--min-samples must be at least 1.".into());
+        }
+
+        if self.min_samples > self.max_samples {
+            return Err("--min-samples must not be larger than --max-samples.".into());
+        }
+
+        Ok(())
+    }
+
+    /// Number of samples to take for a stage whose first execution took `base_time`.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn samples(&self, base_time: Duration) -> u64 {
+        let samples = self.time.as_nanos() / cmp::max(base_time.as_nanos(), 10);
+        (samples.min(u128::from(u64::MAX)) as u64).clamp(self.min_samples, self.max_samples)
+    }
+
+    pub fn warmup_iterations(&self, samples: u64) -> u64 {
+        self.warmup.unwrap_or_else(|| cmp::max(samples / 10, 1))
+    }
+
+    pub fn summary(&self) -> String {
+        let warmup = match self.warmup {
+            Some(warmup) => warmup.to_string(),
+            None => "auto".into(),
+        };
+
+        format!(
+            "budget {}s | {}..={} samples | warmup {warmup}",
+            self.time.as_secs_f64(),
+            self.min_samples,
+            self.max_samples
+        )
+    }
+}
+
+/// Parses a positive number of seconds, e.g. `2.5`.
+pub fn parse_seconds(s: &str) -> Result<Duration, String> {
+    match s.parse::<f64>() {
+        Ok(secs) if secs > 0.0 && secs.is_finite() => Ok(Duration::from_secs_f64(secs)),
+        _ => Err(format!(
+            "Invalid duration \"{s}\", expected a positive number of seconds."
+        )),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&BenchSettings> for JsonValue {
+    fn from(value: &BenchSettings) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "time_secs".into(),
+            JsonValue::Number(value.time.as_secs_f64()),
+        );
+        map.insert(
+            "min_samples".into(),
+            JsonValue::Number(value.min_samples as f64),
+        );
+        map.insert(
+            "max_samples".into(),
+            JsonValue::Number(value.max_samples as f64),
+        );
+        map.insert(
+            "warmup".into(),
+            match value.warmup {
+                Some(x) => JsonValue::Number(x as f64),
+                None => JsonValue::Null,
+            },
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for BenchSettings {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected bench settings to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected bench.{key} to be a number."))
+        };
+
+        let warmup = match json.get("warmup") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(
+                v.get::<f64>()
+                    .copied()
+                    .ok_or("Expected bench.warmup to be null or a number.")? as u64,
+            ),
+        };
+
+        Ok(BenchSettings {
+            time: Duration::try_from_secs_f64(number("time_secs")?).map_err(|e| e.to_string())?,
+            min_samples: number("min_samples")? as u64,
+            max_samples: number("max_samples")? as u64,
+            warmup,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{ffi::OsString, time::Duration};
+
+    use pico_args::Arguments;
+    use tinyjson::JsonValue;
+
+    use super::BenchSettings;
+
+    fn args(args: &[&str]) -> Arguments {
+        Arguments::from_vec(args.iter().map(OsString::from).collect())
+    }
+
+    #[test]
+    fn parses_defaults() {
+        let settings = BenchSettings::from_args(&mut args(&[])).unwrap();
+        assert_eq!(settings, BenchSettings::default());
+    }
+
+    #[test]
+    fn parses_options() {
+        let mut args = args(&[
+            "--bench-time",
+            "2.5",
+            "--min-samples",
+            "5",
+            "--max-samples",
+            "50",
+            "--warmup",
+            "0",
+            "--time",
+        ]);
+        let settings = BenchSettings::from_args(&mut args).unwrap();
+
+        assert_eq!(settings.time, Duration::from_millis(2500));
+        assert_eq!(settings.min_samples, 5);
+        assert_eq!(settings.max_samples, 50);
+        assert_eq!(settings.warmup, Some(0));
+        assert_eq!(args.finish(), vec![OsString::from("--time")]);
+    }
+
+    #[test]
+    fn rejects_invalid_options() {
+        assert!(BenchSettings::from_args(&mut args(&["--bench-time", "-1"])).is_err());
+        assert!(BenchSettings::from_args(&mut args(&["--min-samples", "0"])).is_err());
+        assert!(BenchSettings::from_args(&mut args(&[
+            "--min-samples",
+            "20",
+            "--max-samples",
+            "10"
+        ]))
+        .is_err());
+    }
+
+    #[test]
+    fn roundtrips_args() {
+        let settings = BenchSettings {
+            time: Duration::from_millis(200),
+            min_samples: 1,
+            max_samples: 100,
+            warmup: Some(3),
+        };
+        let formatted = settings.to_args();
+        let mut parsed = Arguments::from_vec(formatted.iter().map(OsString::from).collect());
+        assert_eq!(BenchSettings::from_args(&mut parsed).unwrap(), settings);
+    }
+
+    #[test]
+    fn computes_sample_counts() {
+        let settings = BenchSettings::default();
+        assert_eq!(settings.samples(Duration::from_millis(1)), 1000);
+        assert_eq!(settings.samples(Duration::from_secs(5)), 10);
+        assert_eq!(settings.samples(Duration::ZERO), 10_000);
+        assert_eq!(settings.warmup_iterations(1000), 100);
+        assert_eq!(settings.warmup_iterations(5), 1);
+
+        let settings = BenchSettings {
+            warmup: Some(0),
+            ..BenchSettings::default()
+        };
+        assert_eq!(settings.warmup_iterations(1000), 0);
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let settings = BenchSettings {
+            warmup: Some(7),
+            ..BenchSettings::default()
+        };
+        let json = JsonValue::from(&settings);
+        assert_eq!(BenchSettings::try_from(&json).unwrap(), settings);
+
+        let json = JsonValue::from(&BenchSettings::default());
+        assert_eq!(
+            BenchSettings::try_from(&json).unwrap(),
+            BenchSettings::default()
+        );
+    }
+}
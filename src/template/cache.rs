@@ -0,0 +1,605 @@
+/// Caches downloaded inputs and puzzle descriptions, so that downloading a day again does not hit the server.
+/// The files live in `data/inputs` and `data/puzzles`, `data/cache.json` keeps the response headers and times needed to revalidate them.
+use std::{
+    collections::HashMap,
+    env, fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::aoc_client::{Fetched, Validators};
+use crate::template::backend::AocBackend;
+use crate::template::bench::parse_seconds;
+use crate::template::session;
+use crate::template::submissions::History;
+use crate::template::{year_from_env, Day};
+
+static DATA_DIR: &str = "data";
+static CACHE_FILE_NAME: &str = "cache.json";
+
+const DEFAULT_REQUEST_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The Advent of Code maintainers ask to poll private leaderboards at most every 15 minutes.
+pub const LEADERBOARD_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// Puzzles with only part one are revalidated after this long, in case part two was unlocked outside of the template.
+pub const PUZZLE_TTL: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Resource {
+    Input,
+    Puzzle,
+}
+
+impl Resource {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Resource::Input => "input",
+            Resource::Puzzle => "puzzle",
+        }
+    }
+
+    pub fn path(self, day: Day) -> String {
+        format!("{DATA_DIR}/{}", self.file_name(day))
+    }
+
+    /// The path relative to the data directory.
+    pub fn file_name(self, day: Day) -> String {
+        match self {
+            Resource::Input => format!("inputs/{day}.txt"),
+            Resource::Puzzle => format!("puzzles/{day}.md"),
+        }
+    }
+}
+
+impl FromStr for Resource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "input" => Ok(Resource::Input),
+            "puzzle" => Ok(Resource::Puzzle),
+            _ => Err(format!("Unknown resource `{s}`.")),
+        }
+    }
+}
+
+/// What is known about a downloaded resource.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    pub day: Day,
+    pub resource: Resource,
+    pub validators: Validators,
+    /// Seconds since the unix epoch.
+    pub fetched_at: u64,
+    /// The number of unlocked parts, for puzzles.
+    pub parts: Option<u8>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Metadata {
+    /// Seconds since the unix epoch.
+    pub last_request: Option<f64>,
+    pub data: Vec<Entry>,
+}
+
+impl Metadata {
+    /// Reads the metadata from a file, usually `data/cache.json`. If not present, returns empty metadata.
+    pub fn read_from_file(path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(content) => {
+                Metadata::try_from(content).map_err(|e| format!("{}: {e}", path.display()))
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Metadata::default()),
+            Err(e) => Err(format!("{}: {e}", path.display())),
+        }
+    }
+
+    pub fn store_file(&self, path: &Path) -> io::Result<()> {
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    pub fn find(&self, day: Day, resource: Resource) -> Option<&Entry> {
+        self.data
+            .iter()
+            .find(|entry| entry.day == day && entry.resource == resource)
+    }
+
+    /// Adds an entry, replacing the previous entry of the same resource.
+    pub fn insert(&mut self, entry: Entry) {
+        self.data
+            .retain(|e| e.day != entry.day || e.resource != entry.resource);
+        self.data.push(entry);
+        self.data.sort_by_key(|e| (e.day, e.resource.as_str()));
+    }
+}
+
+/// Whether a cached file can be used without asking the server.
+/// Inputs never change. A puzzle description can only change when part two unlocks, so it is final once it contains both parts.
+/// With only part one, it goes stale once part one was solved after it was fetched (`solved_at`), or once it is older than [`PUZZLE_TTL`].
+/// Times are seconds since the unix epoch.
+pub fn is_fresh(
+    resource: Resource,
+    entry: Option<&Entry>,
+    has_file: bool,
+    solved_at: Option<u64>,
+    now: u64,
+) -> bool {
+    match resource {
+        Resource::Input => has_file,
+        Resource::Puzzle => {
+            has_file
+                && entry.is_some_and(|entry| match entry.parts {
+                    Some(parts) if parts >= 2 => true,
+                    Some(_) => {
+                        solved_at.is_none_or(|solved_at| solved_at < entry.fetched_at)
+                            && now.saturating_sub(entry.fetched_at) < PUZZLE_TTL.as_secs()
+                    }
+                    None => false,
+                })
+        }
+    }
+}
+
+/// How a resource was obtained.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Fetch {
+    /// The cached file is used without a request.
+    Cached,
+    Downloaded,
+    /// The server confirmed that the cached file is up to date.
+    Unchanged,
+}
+
+pub struct Cache<'a> {
+    backend: &'a dyn AocBackend,
+    /// The data directory that the files are written to, `data` outside of tests.
+    dir: PathBuf,
+    /// The event the files belong to, `None` if `AOC_YEAR` is not set.
+    year: Option<u16>,
+    metadata: Metadata,
+    interval: Duration,
+    /// Download all resources again, ignoring the cache.
+    force: bool,
+}
+
+impl<'a> Cache<'a> {
+    /// Opens the cache in `data`, or in `data/accounts/<name>` for the account in `AOC_ACCOUNT`, for the year in `AOC_YEAR`.
+    /// Requests to the Advent of Code servers are spaced out by `AOC_REQUEST_INTERVAL` seconds, 5 by default.
+    pub fn open(backend: &'a dyn AocBackend, force: bool) -> Result<Self, String> {
+        let account = session::account_from_env()?;
+        Cache::open_in(
+            backend,
+            &session::data_dir(account.as_deref()),
+            year_from_env().ok(),
+            force,
+        )
+    }
+
+    /// Opens the cache in another data directory, for the files of `year`.
+    pub fn open_in(
+        backend: &'a dyn AocBackend,
+        dir: &Path,
+        year: Option<u16>,
+        force: bool,
+    ) -> Result<Self, String> {
+        let interval = match env::var("AOC_REQUEST_INTERVAL") {
+            _ if !backend.is_remote() => Duration::ZERO,
+            Ok(value) => parse_seconds(&value).map_err(|e| format!("AOC_REQUEST_INTERVAL: {e}"))?,
+            Err(_) => DEFAULT_REQUEST_INTERVAL,
+        };
+
+        Ok(Cache {
+            backend,
+            dir: dir.to_path_buf(),
+            year,
+            metadata: Metadata::read_from_file(&dir.join(CACHE_FILE_NAME))?,
+            interval,
+            force,
+        })
+    }
+
+    /// Where a resource is written to.
+    pub fn path(&self, resource: Resource, day: Day) -> PathBuf {
+        self.dir.join(resource.file_name(day))
+    }
+
+    pub fn input(&mut self, day: Day) -> Result<Fetch, String> {
+        self.fetch(day, Resource::Input)
+    }
+
+    pub fn puzzle(&mut self, day: Day) -> Result<Fetch, String> {
+        self.fetch(day, Resource::Puzzle)
+    }
+
+    /// Returns the JSON of a private leaderboard from `data/leaderboards/<year>/<id>.json`, downloading it again if it is older than [`LEADERBOARD_INTERVAL`].
+    /// Leaderboards are downloaded for the year of the cache.
+    pub fn leaderboard(&mut self, id: u64) -> Result<(String, Fetch), String> {
+        // without a year, explains how to set `AOC_YEAR`.
+        let year = self.year.map_or_else(year_from_env, Ok)?;
+        let dir = self.dir.join("leaderboards").join(year.to_string());
+        let path = dir.join(format!("{id}.json"));
+
+        let age = fs::metadata(&path)
+            .and_then(|m| m.modified())
+            .map(|modified| modified.elapsed().unwrap_or_default());
+
+        if let Ok(age) = age {
+            if age < LEADERBOARD_INTERVAL {
+                return fs::read_to_string(&path)
+                    .map(|json| (json, Fetch::Cached))
+                    .map_err(|e| format!("Could not read \"{}\": {e}", path.display()));
+            }
+        }
+
+        self.wait();
+        let result = self.backend.leaderboard(id);
+        self.store_metadata()?;
+
+        let json = result.map_err(|e| e.to_string())?;
+        fs::create_dir_all(&dir)
+            .and_then(|()| fs::write(&path, &json))
+            .map_err(|e| format!("Could not write \"{}\": {e}", path.display()))?;
+
+        Ok((json, Fetch::Downloaded))
+    }
+
+    fn fetch(&mut self, day: Day, resource: Resource) -> Result<Fetch, String> {
+        let path = self.path(resource, day);
+        let has_file = fs::metadata(&path).is_ok_and(|m| m.len() > 0);
+        let entry = self.metadata.find(day, resource);
+
+        // part two unlocks once part one is solved.
+        let solved_at = match resource {
+            Resource::Puzzle => self
+                .year
+                .and_then(|year| History::read_in(&self.dir, year).ok())
+                .and_then(|history| history.solved_at(day, 1)),
+            Resource::Input => None,
+        };
+
+        if !self.force && is_fresh(resource, entry, has_file, solved_at, unix_time() as u64) {
+            return Ok(Fetch::Cached);
+        }
+
+        let validators = match entry {
+            Some(entry) if has_file && !self.force => entry.validators.clone(),
+            _ => Validators::default(),
+        };
+
+        self.wait();
+
+        let fetched = match resource {
+            Resource::Input => self
+                .backend
+                .input(day, &validators)
+                .map(|fetched| fetched.map(|input| (input, None))),
+            Resource::Puzzle => self
+                .backend
+                .puzzle(day, &validators)
+                .map(|fetched| fetched.map(|puzzle| (puzzle.markdown, Some(puzzle.parts)))),
+        };
+
+        let result = match fetched {
+            Ok(Fetched::NotModified) => match self.metadata.find(day, resource).cloned() {
+                Some(entry) => {
+                    self.metadata.insert(Entry {
+                        fetched_at: unix_time() as u64,
+                        ..entry
+                    });
+                    Ok(Fetch::Unchanged)
+                }
+                None => Err("The server did not send the resource.".into()),
+            },
+            Ok(Fetched::Modified((content, parts), validators)) => path
+                .parent()
+                .map_or(Ok(()), fs::create_dir_all)
+                .and_then(|()| fs::write(&path, content))
+                .map_err(|e| format!("Could not write \"{}\": {e}", path.display()))
+                .map(|()| {
+                    self.metadata.insert(Entry {
+                        day,
+                        resource,
+                        validators,
+                        fetched_at: unix_time() as u64,
+                        parts,
+                    });
+                    Fetch::Downloaded
+                }),
+            Err(e) => Err(e.to_string()),
+        };
+
+        // store even if the request failed, it still counts towards the request interval.
+        self.store_metadata()?;
+
+        result
+    }
+
+    fn store_metadata(&self) -> Result<(), String> {
+        let path = self.dir.join(CACHE_FILE_NAME);
+        fs::create_dir_all(&self.dir)
+            .and_then(|()| self.metadata.store_file(&path))
+            .map_err(|e| format!("{}: {e}", path.display()))
+    }
+
+    /// Sleeps until the request interval has passed since the last request, which may have been made by an earlier command.
+    fn wait(&mut self) {
+        if let Some(last_request) = self.metadata.last_request {
+            let remaining = self.interval.as_secs_f64() - (unix_time() - last_request);
+            if remaining > 0.0 {
+                println!("Waiting {remaining:.1}s before the next request...");
+                thread::sleep(Duration::from_secs_f64(remaining));
+            }
+        }
+
+        self.metadata.last_request = Some(unix_time());
+    }
+}
+
+fn unix_time() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs_f64())
+        .unwrap_or_default()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Metadata> for JsonValue {
+    fn from(value: &Metadata) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "last_request".into(),
+            match value.last_request {
+                Some(time) => JsonValue::Number(time),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl From<&Entry> for JsonValue {
+    fn from(value: &Entry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        let optional_string = |s: &Option<String>| match s {
+            Some(s) => JsonValue::String(s.clone()),
+            None => JsonValue::Null,
+        };
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert(
+            "resource".into(),
+            JsonValue::String(value.resource.as_str().into()),
+        );
+        map.insert("etag".into(), optional_string(&value.validators.etag));
+        map.insert(
+            "last_modified".into(),
+            optional_string(&value.validators.last_modified),
+        );
+        map.insert(
+            "fetched_at".into(),
+            JsonValue::Number(value.fetched_at as f64),
+        );
+        map.insert(
+            "parts".into(),
+            match value.parts {
+                Some(parts) => JsonValue::Number(f64::from(parts)),
+                None => JsonValue::Null,
+            },
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Metadata {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let last_request = match json.get("last_request") {
+            None | Some(JsonValue::Null) => None,
+            Some(JsonValue::Number(time)) => Some(*time),
+            Some(_) => return Err("expected `json.last_request` to be a number.".into()),
+        };
+
+        let json_data = json
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Metadata {
+            last_request,
+            data: json_data
+                .iter()
+                .map(Entry::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl TryFrom<&JsonValue> for Entry {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected cache entry to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected entry.day to be a Day struct.")?;
+
+        let resource = json
+            .get("resource")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected entry.resource to be a string.")?
+            .parse()?;
+
+        let optional_string = |key: &str| match json.get(key) {
+            None | Some(JsonValue::Null) => Ok(None),
+            Some(JsonValue::String(s)) => Ok(Some(s.clone())),
+            Some(_) => Err(format!("Expected entry.{key} to be null or a string.")),
+        };
+
+        let fetched_at = json
+            .get("fetched_at")
+            .and_then(|v| v.get::<f64>())
+            .ok_or("Expected entry.fetched_at to be a number.")?;
+
+        let parts = match json.get("parts") {
+            None | Some(JsonValue::Null) => None,
+            Some(JsonValue::Number(parts)) => Some(*parts as u8),
+            Some(_) => return Err("Expected entry.parts to be null or a number.".into()),
+        };
+
+        Ok(Entry {
+            day,
+            resource,
+            validators: Validators {
+                etag: optional_string("etag")?,
+                last_modified: optional_string("last_modified")?,
+            },
+            fetched_at: *fetched_at as u64,
+            parts,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{is_fresh, Entry, Metadata, Resource, PUZZLE_TTL};
+    use crate::day;
+    use crate::template::aoc_client::Validators;
+
+    fn puzzle(parts: u8) -> Entry {
+        Entry {
+            day: day!(5),
+            resource: Resource::Puzzle,
+            validators: Validators {
+                etag: Some("\"abc\"".into()),
+                last_modified: None,
+            },
+            fetched_at: 1_733_374_800,
+            parts: Some(parts),
+        }
+    }
+
+    #[test]
+    fn keeps_final_files() {
+        let now = puzzle(2).fetched_at + 10;
+
+        assert!(is_fresh(Resource::Input, None, true, None, now));
+        assert!(!is_fresh(Resource::Input, None, false, None, now));
+
+        assert!(is_fresh(
+            Resource::Puzzle,
+            Some(&puzzle(2)),
+            true,
+            None,
+            now
+        ));
+        assert!(!is_fresh(
+            Resource::Puzzle,
+            Some(&puzzle(2)),
+            false,
+            None,
+            now
+        ));
+        assert!(!is_fresh(Resource::Puzzle, None, true, None, now));
+    }
+
+    #[test]
+    fn keeps_part_one_until_solved_or_expired() {
+        let entry = puzzle(1);
+        let fetched_at = entry.fetched_at;
+        let now = fetched_at + 10;
+
+        assert!(is_fresh(Resource::Puzzle, Some(&entry), true, None, now));
+        assert!(is_fresh(
+            Resource::Puzzle,
+            Some(&entry),
+            true,
+            Some(fetched_at - 1),
+            now
+        ));
+        assert!(!is_fresh(
+            Resource::Puzzle,
+            Some(&entry),
+            true,
+            Some(fetched_at + 5),
+            now
+        ));
+        assert!(!is_fresh(
+            Resource::Puzzle,
+            Some(&entry),
+            true,
+            None,
+            fetched_at + PUZZLE_TTL.as_secs()
+        ));
+    }
+
+    #[test]
+    fn replaces_entries() {
+        let mut metadata = Metadata::default();
+        metadata.insert(puzzle(1));
+        metadata.insert(Entry {
+            resource: Resource::Input,
+            parts: None,
+            ..puzzle(1)
+        });
+        metadata.insert(puzzle(2));
+
+        assert_eq!(metadata.data.len(), 2);
+        assert_eq!(metadata.data[0].resource, Resource::Input);
+        assert_eq!(
+            metadata.find(day!(5), Resource::Puzzle).unwrap().parts,
+            Some(2)
+        );
+        assert_eq!(metadata.find(day!(6), Resource::Puzzle), None);
+    }
+
+    #[test]
+    fn roundtrips_metadata() {
+        let mut metadata = Metadata {
+            last_request: Some(1_733_374_805.5),
+            data: vec![],
+        };
+        metadata.insert(puzzle(1));
+        metadata.insert(Entry {
+            resource: Resource::Input,
+            validators: Validators::default(),
+            parts: None,
+            ..puzzle(1)
+        });
+
+        let json = JsonValue::from(&metadata).stringify().unwrap();
+        assert_eq!(Metadata::try_from(json).unwrap(), metadata);
+    }
+}
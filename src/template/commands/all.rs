@@ -1,5 +1,10 @@
-use crate::template::{all_days, run_multi::run_multi};
+use crate::template::{all_days, run_multi::run_multi, runner::RunOptions};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+pub fn handle(is_release: bool, count_allocations: bool, options: &RunOptions) {
+    run_multi(
+        &all_days().collect(),
+        is_release,
+        count_allocations,
+        options,
+    );
 }
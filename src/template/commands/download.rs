@@ -1,14 +1,336 @@
-use crate::template::{aoc_cli, Day};
-use std::process;
+use crate::template::backend;
+use crate::template::cache::{Cache, Fetch, Resource};
+use crate::template::commands::scaffold;
+use crate::template::session;
+use crate::template::{year_from_env, Day, DayRange};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+/// Downloads the input and puzzle description of a day, unless they are cached already.
+/// `force` downloads both again regardless of the cache.
+pub fn handle(day: Day, force: bool) {
+    let backend = match backend::from_env() {
+        Ok(backend) => backend,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    let mut cache = match Cache::open(backend.as_ref(), force) {
+        Ok(cache) => cache,
+        Err(e) => {
+            eprintln!("Failed to open the download cache: {e}");
+            process::exit(1);
+        }
+    };
+
+    if let Err(e) = download(&mut cache, day) {
+        eprintln!("{e}");
         process::exit(1);
     }
+}
+
+/// What happened to a day of a bulk download.
+#[derive(Debug, PartialEq, Eq)]
+enum Outcome {
+    Downloaded,
+    /// Both resources were cached already.
+    Present,
+    /// The day unlocks in the future, nothing was requested.
+    Locked,
+    Failed(String),
+}
+
+/// Downloads the inputs and puzzle descriptions of a range of days, e.g. to practise on a past event.
+/// Requests are spaced out by the cache, days that are cached already are skipped, and failing days do not stop the others.
+/// `year` downloads another event than `AOC_YEAR`, into `data/years/<year>`. `scaffold` creates the solution files that are missing.
+pub fn handle_all(days: DayRange, year: Option<u16>, scaffold: bool, force: bool) {
+    let configured = year_from_env().ok();
+
+    let account = match session::account_from_env() {
+        Ok(account) => account,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+    let base = session::data_dir(account.as_deref());
+    let dir = data_dir(&base, year, configured);
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    if scaffold && dir != base {
+        eprintln!("The solutions in src/bin are for AOC_YEAR, --scaffold cannot be combined with another --year.");
         process::exit(1);
+    }
+
+    let year = year.or(configured);
+
+    let backend = match backend::for_year(year) {
+        Ok(backend) => backend,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
     };
+
+    let mut cache = match Cache::open_in(backend.as_ref(), &dir, year, force) {
+        Ok(cache) => cache,
+        Err(e) => {
+            eprintln!("Failed to open the download cache: {e}");
+            process::exit(1);
+        }
+    };
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+
+    let outcomes: Vec<(Day, Outcome)> = days
+        .days()
+        .map(|day| {
+            if year.is_some_and(|year| unlocks_at(year, day) > now) {
+                return (day, Outcome::Locked);
+            }
+
+            if scaffold && !Path::new(&scaffold::module_path(day)).exists() {
+                if let Err(e) = scaffold::scaffold(day, false) {
+                    return (day, Outcome::Failed(e));
+                }
+            }
+
+            (day, download_day(&mut cache, day, force))
+        })
+        .collect();
+
+    println!("---");
+    println!("{}", report(&outcomes));
+
+    if outcomes
+        .iter()
+        .any(|(_, outcome)| matches!(outcome, Outcome::Failed(_)))
+    {
+        process::exit(1);
+    }
+}
+
+/// The data directory of a bulk download. Events other than the one in `AOC_YEAR` go to `<base>/years/<year>`,
+/// also if `AOC_YEAR` is not set, so that they do not mix with the files of the solutions in `src/bin`.
+fn data_dir(base: &Path, year: Option<u16>, configured: Option<u16>) -> PathBuf {
+    match year {
+        Some(year) if configured != Some(year) => base.join("years").join(year.to_string()),
+        _ => base.to_path_buf(),
+    }
+}
+
+/// Unlike [`download`], skips days whose input and puzzle are both present, even if the puzzle lacks part two.
+fn download_day(cache: &mut Cache, day: Day, force: bool) -> Outcome {
+    let present = [Resource::Input, Resource::Puzzle]
+        .into_iter()
+        .all(|resource| fs::metadata(cache.path(resource, day)).is_ok_and(|m| m.len() > 0));
+    if present && !force {
+        println!("🎄 Day {day} is present already, skipping it.");
+        return Outcome::Present;
+    }
+
+    match download(cache, day) {
+        Ok(fetches) if fetches.contains(&Fetch::Downloaded) => Outcome::Downloaded,
+        Ok(_) => Outcome::Present,
+        Err(e) => {
+            eprintln!("{e}");
+            Outcome::Failed(e)
+        }
+    }
+}
+
+/// The unix time at which a day unlocks: midnight in the UTC-5 timezone of the Advent of Code servers.
+fn unlocks_at(year: u16, day: Day) -> u64 {
+    let leap = year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400));
+    let years = u64::from(year) - 1;
+    // days from 0001-01-01 to the 1st of december, minus those to 1970-01-01.
+    let days_since_epoch =
+        years * 365 + years / 4 - years / 100 + years / 400 + 334 + u64::from(leap) - 719_162;
+
+    (days_since_epoch + u64::from(day.into_inner()) - 1) * 86_400 + 5 * 3_600
+}
+
+/// Summarizes a bulk download: how many days were downloaded, skipped and not unlocked, and why days failed.
+fn report(outcomes: &[(Day, Outcome)]) -> String {
+    let count = |f: fn(&Outcome) -> bool| outcomes.iter().filter(|(_, o)| f(o)).count();
+    let downloaded = count(|o| *o == Outcome::Downloaded);
+    let present = count(|o| *o == Outcome::Present);
+    let locked = count(|o| *o == Outcome::Locked);
+
+    let mut report = format!(
+        "🎄 Downloaded {downloaded} day(s), skipped {present} day(s) that were present already."
+    );
+    if locked > 0 {
+        report += &format!(" {locked} day(s) are not unlocked yet.");
+    }
+
+    let failed: Vec<&str> = outcomes
+        .iter()
+        .filter_map(|(_, outcome)| match outcome {
+            Outcome::Failed(e) => Some(e.as_str()),
+            _ => None,
+        })
+        .collect();
+    if !failed.is_empty() {
+        report += &format!(
+            "\n✖ {} day(s) failed, run the command again to retry them:",
+            failed.len()
+        );
+        for e in failed {
+            report += &format!("\n  {e}");
+        }
+    }
+
+    report
+}
+
+/// Downloads both resources of a day through the cache, printing how each was obtained.
+fn download(cache: &mut Cache, day: Day) -> Result<[Fetch; 2], String> {
+    let mut fetches = [Fetch::Cached; 2];
+
+    for (i, resource) in [Resource::Input, Resource::Puzzle].into_iter().enumerate() {
+        let path = cache.path(resource, day);
+        let path = path.display();
+
+        let fetch = match resource {
+            Resource::Input => cache.input(day),
+            Resource::Puzzle => cache.puzzle(day),
+        };
+
+        fetches[i] = match fetch {
+            Ok(fetch) => fetch,
+            Err(e) => {
+                return Err(format!(
+                    "Failed to download the {} of day {day}: {e}",
+                    resource.as_str()
+                ))
+            }
+        };
+
+        match fetches[i] {
+            Fetch::Downloaded => {
+                println!("🎄 Successfully wrote {} to \"{path}\".", resource.as_str());
+            }
+            Fetch::Unchanged => {
+                println!("🎄 The {} in \"{path}\" is up to date.", resource.as_str());
+            }
+            Fetch::Cached => println!(
+                "🎄 Using the cached {} in \"{path}\", pass --force to download it again.",
+                resource.as_str()
+            ),
+        }
+    }
+
+    Ok(fetches)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{fs, path::Path};
+
+    use super::{data_dir, download, download_day, report, unlocks_at, Outcome};
+    use crate::day;
+    use crate::template::cache::Fetch;
+    use crate::template::fixtures::tests::Sandbox;
+
+    #[test]
+    fn downloads_through_the_cache() {
+        let sandbox = Sandbox::new("download");
+        let data = &sandbox.data;
+
+        let mut cache = sandbox.cache();
+        assert_eq!(
+            download(&mut cache, day!(1)),
+            Ok([Fetch::Downloaded, Fetch::Downloaded])
+        );
+        assert_eq!(
+            fs::read_to_string(data.join("inputs/01.txt")).unwrap(),
+            "1   2\n"
+        );
+        assert!(!fs::read_to_string(data.join("puzzles/01.md"))
+            .unwrap()
+            .contains("Part Two"));
+
+        // the puzzle is downloaded again once part one is solved.
+        sandbox.solve_part_one();
+        let mut cache = sandbox.cache();
+        assert_eq!(
+            download(&mut cache, day!(1)),
+            Ok([Fetch::Cached, Fetch::Downloaded])
+        );
+        assert_eq!(
+            download(&mut cache, day!(1)),
+            Ok([Fetch::Cached, Fetch::Cached])
+        );
+        assert!(fs::read_to_string(data.join("puzzles/01.md"))
+            .unwrap()
+            .contains("## --- Part Two ---"));
+    }
+
+    #[test]
+    fn fails_for_missing_days() {
+        let sandbox = Sandbox::new("download-missing");
+
+        let mut cache = sandbox.cache();
+        assert_eq!(
+            download(&mut cache, day!(2)),
+            Err(
+                "Failed to download the input of day 02: The server responded with status 404."
+                    .into()
+            )
+        );
+    }
+
+    #[test]
+    fn continues_past_failing_days() {
+        let sandbox = Sandbox::new("download-bulk");
+
+        let mut cache = sandbox.cache();
+        let mut outcomes: Vec<_> = [day!(1), day!(2), day!(1)]
+            .into_iter()
+            .map(|day| (day, download_day(&mut cache, day, false)))
+            .collect();
+
+        assert_eq!(outcomes[0].1, Outcome::Downloaded);
+        assert!(matches!(&outcomes[1].1, Outcome::Failed(e) if e.contains("day 02")));
+        assert_eq!(outcomes[2].1, Outcome::Present);
+
+        outcomes.push((day!(3), Outcome::Locked));
+        assert_eq!(
+            report(&outcomes),
+            "🎄 Downloaded 1 day(s), skipped 1 day(s) that were present already. 1 day(s) are not unlocked yet.
+✖ 1 day(s) failed, run the command again to retry them:
+  Failed to download the input of day 02: The server responded with status 404."
+        );
+    }
+
+    #[test]
+    fn downloads_other_years_into_their_own_directory() {
+        let base = Path::new("data");
+        let years = Path::new("data/years/2015");
+
+        assert_eq!(data_dir(base, None, Some(2023)), base);
+        assert_eq!(data_dir(base, Some(2023), Some(2023)), base);
+        assert_eq!(data_dir(base, Some(2015), Some(2023)), years);
+        // without AOC_YEAR, there is no event that the year could belong to.
+        assert_eq!(data_dir(base, Some(2015), None), years);
+        assert_eq!(data_dir(base, None, None), base);
+    }
+
+    #[test]
+    fn computes_unlock_times() {
+        // 2024-12-01 05:00 UTC, the first day of a leap year's event.
+        assert_eq!(unlocks_at(2024, day!(1)), 1_733_029_200);
+        // 2023-12-25 05:00 UTC.
+        assert_eq!(unlocks_at(2023, day!(25)), 1_703_480_400);
+    }
 }
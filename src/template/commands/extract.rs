@@ -0,0 +1,260 @@
+use std::{
+    fs,
+    io::{stdin, stdout, IsTerminal, Write},
+    process,
+};
+
+use crate::template::cache::Resource;
+use crate::template::examples::Manifest;
+use crate::template::puzzle::{self, Guess};
+use crate::template::Day;
+
+/// Finds the examples of a day in its downloaded puzzle description.
+/// Writes them to `data/examples`, records their answers in the example manifest, and fills in the scaffolded tests.
+/// Existing example files with other contents are kept unless `overwrite` is set.
+pub fn handle(day: Day, overwrite: bool) {
+    let puzzle_path = Resource::Puzzle.path(day);
+    let markdown = match fs::read_to_string(&puzzle_path) {
+        Ok(markdown) => markdown,
+        Err(e) => {
+            eprintln!("Could not read \"{puzzle_path}\": {e}. Run `cargo download {day}` first.");
+            process::exit(1);
+        }
+    };
+
+    let mut manifest = match Manifest::read(day) {
+        Ok(manifest) => manifest.unwrap_or_default(),
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    let module_path = format!("src/bin/{day}.rs");
+    let mut part_one_input: Option<String> = None;
+
+    for part in [1, 2] {
+        let Some(found) = puzzle::find(&markdown, part) else {
+            println!("Part {part} is not unlocked yet.");
+            continue;
+        };
+
+        // part two often reuses the example of part one.
+        let input = match (part, found.input) {
+            (2, Guess::None) => None,
+            (_, guess) => resolve(
+                guess,
+                &format!("the example input of part {part}"),
+                preview_input,
+            ),
+        };
+
+        let (file, file_part, input) = match (part, input) {
+            (1, Some(input)) => (format!("{day}.txt"), None, input),
+            (2, Some(input)) if part_one_input.as_ref() != Some(&input) => {
+                (format!("{day}-2.txt"), Some(2), input)
+            }
+            (2, _) if part_one_input.is_some() => (
+                format!("{day}.txt"),
+                None,
+                part_one_input.clone().unwrap_or_default(),
+            ),
+            _ => continue,
+        };
+
+        if !write_example(&file, &input, overwrite) {
+            continue;
+        }
+        if part == 1 {
+            part_one_input = Some(input);
+        }
+
+        let Some(answer) = resolve(
+            found.answer,
+            &format!("the example answer of part {part}"),
+            str::to_string,
+        ) else {
+            continue;
+        };
+
+        manifest.set(&file, part, &answer);
+
+        let filled = fs::read_to_string(&module_path)
+            .ok()
+            .and_then(|source| fill_in_test(&source, part, &answer, file_part));
+        if let Some(source) = filled {
+            match fs::write(&module_path, source) {
+                Ok(()) => println!("Filled in the part {part} test in \"{module_path}\"."),
+                Err(e) => eprintln!("Failed to write \"{module_path}\": {e}"),
+            }
+        }
+    }
+
+    if manifest.examples.is_empty() {
+        return;
+    }
+
+    match manifest.store(day) {
+        Ok(()) => println!(
+            "Saved the example answers to \"{}\".",
+            Manifest::path(day).display()
+        ),
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Writes an example file, unless it already has other contents. Returns whether the file now holds `input`.
+fn write_example(file: &str, input: &str, overwrite: bool) -> bool {
+    let path = format!("data/examples/{file}");
+
+    match fs::read_to_string(&path) {
+        Ok(existing) if existing == input => return true,
+        Ok(existing) if !existing.trim().is_empty() && !overwrite => {
+            println!("Keeping the existing \"{path}\", pass --overwrite to replace it.");
+            return false;
+        }
+        _ => {}
+    }
+
+    match fs::write(&path, input) {
+        Ok(()) => {
+            println!("🎄 Successfully wrote example to \"{path}\".");
+            true
+        }
+        Err(e) => {
+            eprintln!("Failed to write \"{path}\": {e}");
+            false
+        }
+    }
+}
+
+/// Turns a guess into a value, asking on the terminal if there are several candidates.
+/// Without a terminal, the most likely candidate is used.
+fn resolve(guess: Guess, what: &str, preview: impl Fn(&str) -> String) -> Option<String> {
+    let candidates = match guess {
+        Guess::Sure(value) => return Some(value),
+        Guess::None => {
+            println!("Could not find {what}.");
+            return None;
+        }
+        Guess::Unsure(candidates) => candidates,
+    };
+
+    if !stdin().is_terminal() {
+        println!("Guessing {what}: {}", preview(&candidates[0]));
+        return candidates.into_iter().next();
+    }
+
+    println!("Which is {what}?");
+    for (i, candidate) in candidates.iter().enumerate() {
+        println!("  {}) {}", i + 1, preview(candidate));
+    }
+    println!("  0) none of these");
+
+    loop {
+        print!("Pick one [1]: ");
+        let _ = stdout().flush();
+
+        let mut line = String::new();
+        if stdin().read_line(&mut line).is_err() {
+            return None;
+        }
+
+        match line.trim() {
+            "" => return candidates.into_iter().next(),
+            "0" => return None,
+            choice => match choice.parse::<usize>() {
+                Ok(i) if (1..=candidates.len()).contains(&i) => {
+                    return candidates.into_iter().nth(i - 1)
+                }
+                _ => println!("Enter a number between 0 and {}.", candidates.len()),
+            },
+        }
+    }
+}
+
+fn preview_input(input: &str) -> String {
+    let lines = input.lines().count();
+    let first = input.lines().next().unwrap_or_default();
+
+    if lines > 1 {
+        format!("{first} … ({lines} lines)")
+    } else {
+        first.to_string()
+    }
+}
+
+/// Replaces the `assert_eq!(result, None);` of a scaffolded test with the expected answer.
+/// If the part has its own example file, the test is pointed to it. Returns `None` if the test is not scaffolded.
+fn fill_in_test(source: &str, part: u8, answer: &str, file_part: Option<u8>) -> Option<String> {
+    let name = match part {
+        1 => "fn test_part_one()",
+        2 => "fn test_part_two()",
+        _ => return None,
+    };
+
+    let start = source.find(name)?;
+    let end = start + source[start..].find("\n    }")?;
+    let body = &source[start..end];
+
+    if !body.contains("assert_eq!(result, None);") {
+        return None;
+    }
+
+    let literal = match answer.parse::<i64>() {
+        Ok(_) => answer.to_string(),
+        Err(_) => format!("{answer:?}"),
+    };
+
+    let mut filled = body.replace(
+        "assert_eq!(result, None);",
+        &format!("assert_eq!(result, Some({literal}));"),
+    );
+    if let Some(file_part) = file_part {
+        filled = filled.replace(
+            r#"read_file("examples", DAY)"#,
+            &format!(r#"read_file_part("examples", DAY, {file_part})"#),
+        );
+    }
+
+    Some(format!("{}{filled}{}", &source[..start], &source[end..]))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::fill_in_test;
+
+    const MODULE_TEMPLATE: &str =
+        include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+
+    #[test]
+    fn fills_in_tests() {
+        let source = fill_in_test(MODULE_TEMPLATE, 1, "11", None).unwrap();
+        let source = fill_in_test(&source, 2, "CMZ", Some(2)).unwrap();
+
+        assert!(source.contains(
+            r#"let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, Some(11));"#
+        ));
+        assert!(source.contains(
+            r#"let result = part_two(&advent_of_code::template::read_file_part("examples", DAY, 2));
+        assert_eq!(result, Some("CMZ"));"#
+        ));
+    }
+
+    #[test]
+    fn keeps_filled_in_tests() {
+        let source = fill_in_test(MODULE_TEMPLATE, 1, "11", None).unwrap();
+
+        assert_eq!(fill_in_test(&source, 1, "12", None), None);
+        assert_eq!(
+            fill_in_test("advent_of_code::example_tests!();", 1, "12", None),
+            None
+        );
+    }
+}
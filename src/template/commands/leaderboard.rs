@@ -0,0 +1,205 @@
+use std::process;
+
+use crate::day;
+use crate::template::backend;
+use crate::template::cache::{Cache, Fetch};
+use crate::template::leaderboard::Leaderboard;
+use crate::template::{Day, ANSI_BOLD, ANSI_GREEN, ANSI_ITALIC, ANSI_RESET, ANSI_YELLOW};
+
+/// Prints a private leaderboard as a table of members, their stars per day and local score.
+/// The delta column shows the time between the two stars of `day`, the last day with stars by default.
+pub fn handle(id: u64, day: Option<Day>) {
+    let backend = match backend::from_env() {
+        Ok(backend) => backend,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    let loaded = Cache::open(backend.as_ref(), false).and_then(|mut cache| load(&mut cache, id));
+    let (leaderboard, fetch) = match loaded {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    if leaderboard.members.is_empty() {
+        println!("Leaderboard {id} has no members.");
+        return;
+    }
+
+    let delta_day = day
+        .or_else(|| leaderboard.last_day().and_then(Day::new))
+        .unwrap_or(day!(1));
+    let days = leaderboard
+        .last_day()
+        .unwrap_or(1)
+        .max(delta_day.into_inner());
+
+    println!("{}", format_table(&leaderboard, days, delta_day));
+
+    if fetch == Fetch::Cached {
+        println!(
+            "\n{ANSI_ITALIC}Cached, private leaderboards are downloaded at most every 15 minutes.{ANSI_RESET}"
+        );
+    }
+}
+
+/// Reads a private leaderboard through the cache.
+pub(crate) fn load(cache: &mut Cache, id: u64) -> Result<(Leaderboard, Fetch), String> {
+    let (json, fetch) = cache
+        .leaderboard(id)
+        .map_err(|e| format!("Failed to download leaderboard {id}: {e}"))?;
+
+    let leaderboard = Leaderboard::try_from(json.as_str()).map_err(|e| {
+        format!("Leaderboard {id} could not be read, {e} Is your session a member of it?")
+    })?;
+
+    Ok((leaderboard, fetch))
+}
+
+fn format_table(leaderboard: &Leaderboard, days: u8, delta_day: Day) -> String {
+    let tens: String = (1..=days)
+        .map(|day| match day / 10 {
+            0 => ' ',
+            tens => char::from(b'0' + tens),
+        })
+        .collect();
+    let ones: String = (1..=days).map(|day| char::from(b'0' + day % 10)).collect();
+
+    let fastest = leaderboard
+        .members
+        .iter()
+        .filter_map(|member| member.delta(delta_day.into_inner()))
+        .min();
+
+    let mut lines = vec![];
+    if days >= 10 {
+        lines.push(format!("{ANSI_BOLD}{:12}{tens}{ANSI_RESET}", ""));
+    }
+    lines.push(format!(
+        "{ANSI_BOLD}{:>3}  {:>5}  {ones}  {:>9}  Name{ANSI_RESET}",
+        "#",
+        "Score",
+        format!("Δ day {}", delta_day.into_inner())
+    ));
+
+    for (rank, member) in leaderboard.members.iter().enumerate() {
+        let stars: String = (1..=days)
+            .map(|day| match member.stars_on(day) {
+                2 => format!("{ANSI_YELLOW}*{ANSI_RESET}"),
+                1 => "*".into(),
+                _ => ".".into(),
+            })
+            .collect();
+
+        let delta = match member.delta(delta_day.into_inner()) {
+            Some(delta) if Some(delta) == fastest => {
+                format!("{ANSI_GREEN}{:>9}{ANSI_RESET}", format_delta(delta))
+            }
+            Some(delta) => format!("{:>9}", format_delta(delta)),
+            None => format!("{:>9}", "-"),
+        };
+
+        lines.push(format!(
+            "{:>3}) {:>5}  {stars}  {delta}  {}",
+            rank + 1,
+            member.local_score,
+            member.display_name()
+        ));
+    }
+
+    lines.join("\n")
+}
+
+fn format_delta(seconds: u64) -> String {
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+
+    if hours > 0 {
+        format!("{hours}h{minutes:02}m")
+    } else if minutes > 0 {
+        format!("{minutes}m{seconds:02}s")
+    } else {
+        format!("{seconds}s")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{collections::BTreeMap, env};
+
+    use super::{format_delta, format_table, load};
+    use crate::day;
+    use crate::template::cache::Fetch;
+    use crate::template::fixtures::tests::Sandbox;
+    use crate::template::leaderboard::{Leaderboard, Member};
+
+    fn member(id: u64, name: Option<&str>, days: &[(u8, [Option<u64>; 2])]) -> Member {
+        Member {
+            id,
+            name: name.map(Into::into),
+            local_score: 10 * id,
+            stars: 0,
+            days: days.iter().copied().collect::<BTreeMap<_, _>>(),
+        }
+    }
+
+    #[test]
+    fn formats_tables() {
+        let leaderboard = Leaderboard {
+            members: vec![
+                member(
+                    2,
+                    Some("Ada"),
+                    &[(1, [Some(0), Some(300)]), (11, [Some(0), None])],
+                ),
+                member(1, None, &[(11, [Some(0), Some(75)])]),
+            ],
+        };
+
+        assert_eq!(
+            format_table(&leaderboard, 11, day!(11)),
+            [
+                "\x1b[1m                     11\x1b[0m",
+                "\x1b[1m  #  Score  12345678901   Δ day 11  Name\x1b[0m",
+                "  1)    20  \x1b[33m*\x1b[0m.........*          -  Ada",
+                "  2)    10  ..........\x1b[33m*\x1b[0m  \x1b[32m    1m15s\x1b[0m  (anonymous user #1)",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn loads_through_the_cache() {
+        let sandbox = Sandbox::new("leaderboard");
+        let mut cache = sandbox.cache();
+
+        let (leaderboard, fetch) = load(&mut cache, 7).unwrap();
+        assert_eq!(fetch, Fetch::Downloaded);
+        assert_eq!(leaderboard.members[0].display_name(), "Ada");
+        assert_eq!(load(&mut cache, 7).unwrap().1, Fetch::Cached);
+
+        let year = env::var("AOC_YEAR").unwrap();
+        assert!(sandbox
+            .data
+            .join(format!("leaderboards/{year}/7.json"))
+            .exists());
+
+        assert_eq!(
+            load(&mut cache, 8).unwrap_err(),
+            "Failed to download leaderboard 8: The server responded with status 404."
+        );
+    }
+
+    #[test]
+    fn formats_deltas() {
+        assert_eq!(format_delta(42), "42s");
+        assert_eq!(format_delta(125), "2m05s");
+        assert_eq!(format_delta(3725), "1h02m");
+    }
+}
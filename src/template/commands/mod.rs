@@ -1,6 +1,11 @@
 pub mod all;
 pub mod download;
+pub mod extract;
+pub mod leaderboard;
 pub mod read;
 pub mod scaffold;
+pub mod session;
 pub mod solve;
+pub mod stars;
 pub mod time;
+pub mod verify;
@@ -1,15 +1,68 @@
-use std::process;
+use std::{fs, process};
 
-use crate::template::{aoc_cli, Day};
+use crate::template::backend;
+use crate::template::cache::{Cache, Resource};
+use crate::template::Day;
 
+/// Prints the puzzle description of a day, downloading it again if part two may have unlocked.
 pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    let backend = match backend::from_env() {
+        Ok(backend) => backend,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    match Cache::open(backend.as_ref(), false).and_then(|mut cache| read(&mut cache, day)) {
+        Ok(puzzle) => print!("{puzzle}"),
+        Err(e) => {
+            eprintln!("Failed to read day {day}: {e}");
+            process::exit(1);
+        }
     }
+}
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+/// Prints the downloaded puzzle description of a day.
+pub fn print_puzzle(day: Day) {
+    let path = Resource::Puzzle.path(day);
+
+    match fs::read_to_string(&path) {
+        Ok(puzzle) => print!("{puzzle}"),
+        Err(e) => {
+            eprintln!("Could not read \"{path}\": {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Refreshes the puzzle description of a day through the cache and returns it.
+fn read(cache: &mut Cache, day: Day) -> Result<String, String> {
+    cache.puzzle(day)?;
+
+    let path = cache.path(Resource::Puzzle, day);
+    fs::read_to_string(&path).map_err(|e| format!("Could not read \"{}\": {e}", path.display()))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::read;
+    use crate::day;
+    use crate::template::fixtures::tests::Sandbox;
+
+    #[test]
+    fn reads_unlocked_parts() {
+        let sandbox = Sandbox::new("read");
+
+        let mut cache = sandbox.cache();
+        let puzzle = read(&mut cache, day!(1)).unwrap();
+        assert!(puzzle.starts_with("## --- Day 1: Historian Hysteria ---\n\nFor example:"));
+        assert!(!puzzle.contains("Part Two"));
+
+        sandbox.solve_part_one();
+        let puzzle = read(&mut cache, day!(1)).unwrap();
+        assert!(puzzle.contains("## --- Part Two ---\n\nWhat is their similarity score?"));
+    }
 }
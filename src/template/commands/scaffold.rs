@@ -1,6 +1,6 @@
 use std::{
     fs::{File, OpenOptions},
-    io::Write,
+    io::{ErrorKind, Write},
     process,
 };
 
@@ -19,61 +19,59 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
     file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
+/// Creates an empty data file, unless it exists already. Returns whether the file was created.
+fn create_data_file(path: &str) -> Result<bool, std::io::Error> {
+    match OpenOptions::new().write(true).create_new(true).open(path) {
+        Ok(_) => Ok(true),
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => Ok(false),
+        Err(e) => Err(e),
+    }
 }
 
 pub fn handle(day: Day, overwrite: bool) {
+    if let Err(e) = scaffold(day, overwrite) {
+        eprintln!("{e}");
+        process::exit(1);
+    }
+
+    println!("---");
+    println!("🎄 Type `cargo solve {day}` to run your solution.");
+}
+
+/// The solution file of a day.
+pub fn module_path(day: Day) -> String {
+    format!("src/bin/{day}.rs")
+}
+
+/// Creates the solution file of a day from the template, and empty input and example files.
+/// Input and example files that exist already are kept, e.g. inputs that were downloaded before.
+pub fn scaffold(day: Day, overwrite: bool) -> Result<(), String> {
     let input_path = format!("data/inputs/{day}.txt");
     let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+    let module_path = module_path(day);
 
-    let mut file = match safe_create_file(&module_path, overwrite) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
-        }
-    };
+    let mut file = safe_create_file(&module_path, overwrite)
+        .map_err(|e| format!("Failed to create module file: {e}"))?;
 
-    match file.write_all(
+    file.write_all(
         MODULE_TEMPLATE
             .replace("%DAY_NUMBER%", &day.into_inner().to_string())
             .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
-        }
-    }
+    )
+    .map_err(|e| format!("Failed to write module contents: {e}"))?;
+    println!("Created module file \"{}\"", &module_path);
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
-        }
+    if create_data_file(&input_path).map_err(|e| format!("Failed to create input file: {e}"))? {
+        println!("Created empty input file \"{}\"", &input_path);
+    } else {
+        println!("Kept existing input file \"{}\"", &input_path);
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
-        }
+    if create_data_file(&example_path).map_err(|e| format!("Failed to create example file: {e}"))? {
+        println!("Created empty example file \"{}\"", &example_path);
+    } else {
+        println!("Kept existing example file \"{}\"", &example_path);
     }
 
-    println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    Ok(())
 }
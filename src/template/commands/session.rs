@@ -0,0 +1,103 @@
+use std::{
+    env,
+    io::{stdin, stdout, IsTerminal, Write},
+    process,
+};
+
+use crate::template::aoc_client::{AocClient, AocClientError};
+use crate::template::session;
+
+/// Checks whether the session cookies are still accepted, of one account or of the default and all named accounts.
+/// With `refresh`, asks for a new session cookie instead and saves it once the server accepts it.
+pub fn handle(account: Option<String>, refresh: bool) {
+    if let Some(account) = &account {
+        if let Err(e) = session::check_account(account) {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    }
+
+    if refresh {
+        refresh_session(account.as_deref());
+        return;
+    }
+
+    let accounts: Vec<Option<String>> = match account {
+        Some(account) => vec![Some(account)],
+        None => [None]
+            .into_iter()
+            .chain(session::accounts().into_iter().map(Some))
+            .collect(),
+    };
+
+    let mut valid = true;
+    for account in &accounts {
+        let account = account.as_deref();
+        let who = session::describe(account);
+
+        match AocClient::for_account(account).and_then(|client| client.validate()) {
+            Ok(name) => println!("✔ {who}: logged in as {name}."),
+            Err(e) => {
+                println!("✖ {who}: {e}");
+                valid = false;
+            }
+        }
+    }
+
+    if !valid {
+        process::exit(1);
+    }
+}
+
+fn refresh_session(account: Option<&str>) {
+    let who = session::describe(account);
+
+    if stdin().is_terminal() {
+        println!("{}", session::COPY_INSTRUCTIONS);
+        print!("Paste the session cookie of {who}: ");
+        let _ = stdout().flush();
+    }
+
+    let mut line = String::new();
+    if let Err(e) = stdin().read_line(&mut line) {
+        eprintln!("Failed to read the session cookie: {e}");
+        process::exit(1);
+    }
+
+    let name = match AocClient::for_session(account, &line).and_then(|client| client.validate()) {
+        Ok(name) => Some(name),
+        // keep cookies that could not be checked, e.g. while offline.
+        Err(AocClientError::Transport(e)) => {
+            eprintln!("Could not check the session cookie, saving it anyway: {e}");
+            None
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    match session::store(account, &line) {
+        Ok(path) => match name {
+            Some(name) => println!(
+                "Saved the session cookie of {who} to \"{}\", logged in as {name}.",
+                path.display()
+            ),
+            None => println!(
+                "Saved the session cookie of {who} to \"{}\".",
+                path.display()
+            ),
+        },
+        Err(e) => {
+            eprintln!("Failed to save the session cookie: {e}");
+            process::exit(1);
+        }
+    }
+
+    if env::var(session::env_var(account)).is_ok() {
+        println!(
+            "Note: {} is set and takes precedence over the saved cookie.",
+            session::env_var(account)
+        );
+    }
+}
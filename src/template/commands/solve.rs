@@ -1,8 +1,33 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::input::{InputSource, MissingInput};
+use crate::template::{runner::RunOptions, Day};
+
+/// Runs the solution of a day. A missing or empty puzzle input is downloaded first, with `download` without asking.
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    count_allocations: bool,
+    submit_part: Option<u8>,
+    input: &InputSource,
+    download: bool,
+    options: &RunOptions,
+) {
+    // check before compiling the solution, so that a missing input does not wait for the build.
+    if *input == InputSource::Puzzle {
+        let missing = if download {
+            MissingInput::Download
+        } else {
+            MissingInput::Ask
+        };
+        if let Err(e) = input.read_or_download(day, missing) {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    }
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if dhat {
@@ -12,8 +37,16 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
             "--features".to_string(),
             "dhat-heap".to_string(),
         ]);
-    } else if release {
-        cmd_args.push("--release".to_string());
+    } else {
+        if release {
+            cmd_args.push("--release".to_string());
+        }
+
+        // both dhat and the counting allocator install a global allocator, so only one can be active.
+        if count_allocations {
+            cmd_args.push("--features".to_string());
+            cmd_args.push("count-allocations".to_string());
+        }
     }
 
     cmd_args.push("--".to_string());
@@ -23,6 +56,9 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
+    cmd_args.extend(input.to_args());
+    cmd_args.extend(options.to_args());
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
@@ -0,0 +1,91 @@
+use std::{env, process};
+
+use crate::template::answers::Answers;
+use crate::template::aoc_client::AocClientError;
+use crate::template::backend;
+use crate::template::cache::Cache;
+use crate::template::commands::leaderboard;
+use crate::template::readme_benchmarks::Error;
+use crate::template::readme_stars::{self, Stars};
+use crate::template::submissions::History;
+
+/// Rewrites the stars table in the readme.
+/// By default, the stars are read from the local answers and submission history, which works offline.
+/// With `from_leaderboard`, they are read from the private leaderboard that every user owns, identified by `AOC_USER_ID`.
+pub fn handle(from_leaderboard: bool) {
+    let year = match env::var("AOC_YEAR").ok().and_then(|year| year.parse().ok()) {
+        Some(year) => year,
+        None => {
+            eprintln!("{}", AocClientError::MissingYear);
+            process::exit(1);
+        }
+    };
+
+    let stars = if from_leaderboard {
+        leaderboard_stars()
+    } else {
+        local_stars()
+    };
+
+    match readme_stars::update(year, &stars) {
+        Ok(()) => println!("Stored {} star(s) of {year} in the readme.", stars.count()),
+        Err(Error::Parser(e)) => {
+            eprintln!("Failed to update the readme: {e}");
+            process::exit(1);
+        }
+        Err(Error::IO(e)) => {
+            eprintln!("Failed to update the readme: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn local_stars() -> Stars {
+    let local = Answers::read_from_file().and_then(|answers| {
+        History::read_from_file().map(|history| Stars::from_local(&answers, &history))
+    });
+
+    match local {
+        Ok(stars) => stars,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn leaderboard_stars() -> Stars {
+    let Some(id) = env::var("AOC_USER_ID")
+        .ok()
+        .and_then(|id| id.trim().parse::<u64>().ok())
+    else {
+        eprintln!("AOC_USER_ID is not set, set it in .cargo/config.toml to the number on https://adventofcode.com/settings.");
+        process::exit(1);
+    };
+
+    let backend = match backend::from_env() {
+        Ok(backend) => backend,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    let loaded = Cache::open(backend.as_ref(), false)
+        .and_then(|mut cache| leaderboard::load(&mut cache, id));
+    let member = loaded.and_then(|(leaderboard, _)| {
+        leaderboard
+            .members
+            .into_iter()
+            .find(|member| member.id == id)
+            .ok_or_else(|| format!("User {id} is not a member of leaderboard {id}."))
+    });
+
+    match member {
+        Ok(member) => Stars::from_member(&member),
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    }
+}
@@ -1,27 +1,36 @@
 use std::collections::HashSet;
+use std::process;
 
+use crate::template::compare::{compare, print_comparison, regressions, thread_mismatches};
 use crate::template::run_multi::run_multi;
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{all_days, readme_benchmarks, runner::RunOptions, Day};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
+/// Options for comparing fresh timings with the stored timings.
+pub struct Comparison {
+    /// Exit with an error if a stage got slower by more than this many percent.
+    pub fail_on_regression: Option<f64>,
+}
+
+pub fn handle(
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    count_allocations: bool,
+    options: &RunOptions,
+    comparison: Option<&Comparison>,
+) {
     let stored_timings = Timings::read_from_file();
 
-    let days_to_run = day.map_or_else(
-        || {
-            if run_all {
-                all_days().collect()
-            } else {
-                // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
-                    .collect()
-            }
-        },
-        |day| HashSet::from([day]),
-    );
+    let days_to_run = select_days(day, run_all, comparison.is_some(), &stored_timings);
+
+    let timings = run_multi(&days_to_run, true, count_allocations, options).unwrap();
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let deltas = comparison.map(|_| compare(&stored_timings, &timings));
+
+    if let Some(deltas) = &deltas {
+        print_comparison(deltas, &thread_mismatches(&stored_timings, &timings));
+    }
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
@@ -37,4 +46,86 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
             }
         }
     }
+
+    if let (Some(deltas), Some(threshold)) =
+        (&deltas, comparison.and_then(|c| c.fail_on_regression))
+    {
+        let regressed = regressions(deltas, threshold);
+
+        if !regressed.is_empty() {
+            eprintln!(
+                "\n{} stage(s) regressed by more than {threshold}% or failed:",
+                regressed.len()
+            );
+            for delta in regressed {
+                eprintln!("{}", delta.format());
+            }
+            process::exit(1);
+        }
+    }
+}
+
+/// Returns the days to bench. Without a day or `--all`, days that are fully benched are skipped,
+/// unless comparing: then only the days with stored timings are benched, as the others have nothing to compare with.
+fn select_days(
+    day: Option<Day>,
+    run_all: bool,
+    comparing: bool,
+    stored_timings: &Timings,
+) -> HashSet<Day> {
+    if let Some(day) = day {
+        return HashSet::from([day]);
+    }
+
+    if run_all {
+        all_days().collect()
+    } else if comparing {
+        stored_timings
+            .data
+            .iter()
+            .map(|timing| timing.day)
+            .collect()
+    } else {
+        all_days()
+            .filter(|day| !stored_timings.is_day_complete(*day))
+            .collect()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::select_days;
+    use crate::{day, template::timings::Timings};
+
+    fn stored_timings() -> Timings {
+        let json = r#"{ "data": [
+            { "day": "01", "part_1": "1ms", "part_2": "2ms", "total_nanos": 3000000 },
+            { "day": "04", "part_1": "1ms", "part_2": null, "total_nanos": 1000000 }
+        ] }"#;
+        Timings::try_from(json.to_string()).unwrap()
+    }
+
+    #[test]
+    fn compares_stored_days_without_all() {
+        let days = select_days(None, false, true, &stored_timings());
+        assert_eq!(days, HashSet::from([day!(1), day!(4)]));
+    }
+
+    #[test]
+    fn skips_complete_days_without_all() {
+        let days = select_days(None, false, false, &stored_timings());
+        assert_eq!(days.len(), 24);
+        assert!(!days.contains(&day!(1)));
+        assert!(days.contains(&day!(4)));
+    }
+
+    #[test]
+    fn runs_the_given_day() {
+        let days = select_days(Some(day!(1)), false, true, &stored_timings());
+        assert_eq!(days, HashSet::from([day!(1)]));
+    }
 }
@@ -0,0 +1,236 @@
+use std::process;
+
+use crate::template::answers::{Answers, DayAnswers};
+use crate::template::examples::mismatch;
+use crate::template::report::{Record, Stage, Status};
+use crate::template::run_multi::child_commands;
+use crate::template::runner::{Outcome, RunOptions};
+use crate::template::{Day, ANSI_BOLD, ANSI_GREEN, ANSI_RED, ANSI_RESET};
+
+/// The result of comparing a part with its known answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Check {
+    Pass,
+    /// Contains a description of the mismatch.
+    Fail(String),
+    /// There is no known answer for the part.
+    Unknown,
+}
+
+impl Check {
+    fn symbol(&self) -> String {
+        match self {
+            Check::Pass => format!("{ANSI_GREEN}✔{ANSI_RESET}"),
+            Check::Fail(_) => format!("{ANSI_RED}✖{ANSI_RESET}"),
+            Check::Unknown => "-".into(),
+        }
+    }
+}
+
+/// Run every day with known answers in release mode and compare its answers with `data/answers/<year>.json`.
+/// Exits with an error if any part does not match.
+pub fn handle(options: &RunOptions) {
+    let answers = match Answers::read_from_file() {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("Failed to read known answers: {e}");
+            process::exit(1);
+        }
+    };
+
+    if answers.data.is_empty() {
+        println!("No known answers for AOC_YEAR yet.");
+        return;
+    }
+
+    let mut rows: Vec<(Day, [Check; 2])> = vec![];
+
+    for (i, day_answers) in answers.data.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+
+        let day = day_answers.day;
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        let checks = match child_commands::run_solution(day, true, false, options) {
+            Ok(Some(records)) => check_day(day_answers, &records),
+            Ok(None) => fail_known(day_answers, "not scaffolded"),
+            Err(e) => fail_known(day_answers, &format!("failed to run: {e:?}")),
+        };
+
+        rows.push((day, checks));
+    }
+
+    println!("\n{ANSI_BOLD}Verified answers:{ANSI_RESET}");
+    println!("{}", format_matrix(&rows));
+
+    let failures = format_failures(&rows);
+    if !failures.is_empty() {
+        eprintln!("\n{} part(s) do not match:", failures.len());
+        for failure in failures {
+            eprintln!("{failure}");
+        }
+        process::exit(1);
+    }
+}
+
+/// Compares the records of a day with its known answers.
+pub fn check_day(answers: &DayAnswers, records: &[Record]) -> [Check; 2] {
+    [1, 2].map(|part| {
+        let Some(expected) = answers.get(part) else {
+            return Check::Unknown;
+        };
+
+        let Some(record) = records.iter().find(|r| r.stage == Stage::Part(part)) else {
+            return Check::Fail(format!("expected {expected}, got no result"));
+        };
+
+        let outcome = match record.status {
+            Status::Ok => Outcome::Answer(record.answer.clone().unwrap_or_default()),
+            Status::NoAnswer => Outcome::NoAnswer,
+            Status::Error => Outcome::Error(record.message.clone().unwrap_or_default()),
+            Status::TimedOut => return Check::Fail(format!("expected {expected}, timed out")),
+            Status::Panicked => {
+                return Check::Fail(format!(
+                    "expected {expected}, panicked: {}",
+                    record.message.as_deref().unwrap_or("unknown")
+                ))
+            }
+        };
+
+        match mismatch(&outcome, expected) {
+            None => Check::Pass,
+            Some(mismatch) => Check::Fail(mismatch),
+        }
+    })
+}
+
+/// Fails all parts with a known answer, e.g. because the solution could not be run.
+fn fail_known(answers: &DayAnswers, reason: &str) -> [Check; 2] {
+    [1, 2].map(|part| match answers.get(part) {
+        Some(_) => Check::Fail(reason.into()),
+        None => Check::Unknown,
+    })
+}
+
+fn format_matrix(rows: &[(Day, [Check; 2])]) -> String {
+    let mut lines = vec!["Day  Part 1  Part 2".to_string()];
+
+    for (day, [part_1, part_2]) in rows {
+        lines.push(format!(
+            " {day}  {}       {}",
+            part_1.symbol(),
+            part_2.symbol()
+        ));
+    }
+
+    lines.join("\n")
+}
+
+fn format_failures(rows: &[(Day, [Check; 2])]) -> Vec<String> {
+    rows.iter()
+        .flat_map(|(day, checks)| {
+            checks
+                .iter()
+                .zip(1..)
+                .filter_map(move |(check, part)| match check {
+                    Check::Fail(reason) => Some(format!("Day {day} Part {part}: {reason}")),
+                    _ => None,
+                })
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check_day, format_failures, format_matrix, Check};
+    use crate::{
+        day,
+        template::{
+            answers::DayAnswers,
+            report::{Record, Stage, Status},
+        },
+    };
+
+    fn record(part: u8, answer: Option<&str>, status: Status) -> Record {
+        Record {
+            stage: Stage::Part(part),
+            answer: answer.map(Into::into),
+            nanos: 0.0,
+            samples: 1,
+            status,
+            message: None,
+            stats: None,
+            cpu: None,
+            memory: None,
+            threads: None,
+        }
+    }
+
+    fn answers(part_1: Option<&str>, part_2: Option<&str>) -> DayAnswers {
+        DayAnswers {
+            day: day!(7),
+            part_1: part_1.map(Into::into),
+            part_2: part_2.map(Into::into),
+        }
+    }
+
+    #[test]
+    fn checks_answers() {
+        let records = [
+            record(1, Some("3749"), Status::Ok),
+            record(2, Some("11388"), Status::Ok),
+        ];
+
+        assert_eq!(
+            check_day(&answers(Some("3749"), Some("11387")), &records),
+            [Check::Pass, Check::Fail("expected 11387, got 11388".into())]
+        );
+        assert_eq!(
+            check_day(&answers(None, Some("11388")), &records),
+            [Check::Unknown, Check::Pass]
+        );
+    }
+
+    #[test]
+    fn fails_on_missing_results() {
+        let records = [record(1, None, Status::TimedOut)];
+
+        assert_eq!(
+            check_day(&answers(Some("1"), Some("2")), &records),
+            [
+                Check::Fail("expected 1, timed out".into()),
+                Check::Fail("expected 2, got no result".into())
+            ]
+        );
+    }
+
+    #[test]
+    fn formats_results() {
+        let rows = vec![
+            (day!(1), [Check::Pass, Check::Unknown]),
+            (
+                day!(7),
+                [Check::Pass, Check::Fail("expected 2, got 3".into())],
+            ),
+        ];
+
+        assert_eq!(
+            format_matrix(&rows),
+            [
+                "Day  Part 1  Part 2",
+                " 01  \x1b[32m✔\x1b[0m       -",
+                " 07  \x1b[32m✔\x1b[0m       \x1b[31m✖\x1b[0m",
+            ]
+            .join("\n")
+        );
+        assert_eq!(
+            format_failures(&rows),
+            vec!["Day 07 Part 2: expected 2, got 3".to_string()]
+        );
+    }
+}
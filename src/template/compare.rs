@@ -0,0 +1,274 @@
+/// Compares fresh benchmark timings against the timings stored in `data/timings.json`.
+use crate::template::report::{Stage, Status};
+use crate::template::stats::nanos_to_duration;
+use crate::template::timings::Timings;
+use crate::template::{Day, ANSI_BOLD, ANSI_GREEN, ANSI_RED, ANSI_RESET};
+
+/// The change in runtime of a single stage between the stored and a fresh run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Delta {
+    pub day: Day,
+    pub stage: Stage,
+    pub stored_nanos: f64,
+    /// The runtime of the fresh run, or its status if the stage did not complete.
+    pub fresh: Result<f64, Status>,
+}
+
+impl Delta {
+    /// Relative change in percent, positive values are regressions.
+    /// `None` if the stage did not complete in the fresh run.
+    pub fn percent(&self) -> Option<f64> {
+        let fresh_nanos = self.fresh.ok()?;
+
+        if self.stored_nanos == 0.0 {
+            return Some(0.0);
+        }
+
+        Some((fresh_nanos - self.stored_nanos) / self.stored_nanos * 100.0)
+    }
+
+    /// Formats the delta as a single line, coloured red for regressions and green for improvements.
+    pub fn format(&self) -> String {
+        let fresh_nanos = match self.fresh {
+            Ok(nanos) => nanos,
+            Err(status) => {
+                return format!(
+                    "Day {} {}: {:.1?} → {ANSI_RED}✖ {}{ANSI_RESET}",
+                    self.day,
+                    self.stage,
+                    nanos_to_duration(self.stored_nanos),
+                    describe(status)
+                )
+            }
+        };
+
+        let diff = fresh_nanos - self.stored_nanos;
+        let percent = self.percent().unwrap_or_default();
+
+        let (colour, sign) = if diff > 0.0 {
+            (ANSI_RED, "+")
+        } else if diff < 0.0 {
+            (ANSI_GREEN, "-")
+        } else {
+            ("", "±")
+        };
+
+        format!(
+            "Day {} {}: {:.1?} → {:.1?} {colour}{sign}{:.1?} ({sign}{:.1}%){ANSI_RESET}",
+            self.day,
+            self.stage,
+            nanos_to_duration(self.stored_nanos),
+            nanos_to_duration(fresh_nanos),
+            nanos_to_duration(diff.abs()),
+            percent.abs()
+        )
+    }
+}
+
+fn describe(status: Status) -> &'static str {
+    match status {
+        Status::Ok => "ok",
+        Status::NoAnswer => "no answer",
+        Status::TimedOut => "timed out",
+        Status::Panicked => "panicked",
+        Status::Error => "error",
+    }
+}
+
+/// Pairs up the stages that were timed in `stored` with the same stages in `fresh`.
+/// Stages that no longer complete in `fresh` are included with their status.
+pub fn compare(stored: &Timings, fresh: &Timings) -> Vec<Delta> {
+    let mut deltas = vec![];
+
+    for fresh_timing in &fresh.data {
+        let Some(stored_timing) = stored.data.iter().find(|t| t.day == fresh_timing.day) else {
+            continue;
+        };
+
+        for stage in [Stage::Parse, Stage::Part(1), Stage::Part(2)] {
+            let Some(stored_nanos) = stored_timing.nanos(stage) else {
+                continue;
+            };
+
+            let fresh = match fresh_timing.nanos(stage) {
+                Some(fresh_nanos) => Ok(fresh_nanos),
+                None => match fresh_timing.stage(stage).and_then(|s| s.status) {
+                    Some(status) if status != Status::Ok => Err(status),
+                    _ => continue,
+                },
+            };
+
+            deltas.push(Delta {
+                day: fresh_timing.day,
+                stage,
+                stored_nanos,
+                fresh,
+            });
+        }
+    }
+
+    deltas
+}
+
+/// Returns the days that ran on a different number of threads than their stored timings, as `(day, stored, fresh)`.
+/// Timings of parallel solutions are not comparable in that case.
+pub fn thread_mismatches(stored: &Timings, fresh: &Timings) -> Vec<(Day, u64, u64)> {
+    fresh
+        .data
+        .iter()
+        .filter_map(|fresh_timing| {
+            let stored_timing = stored.data.iter().find(|t| t.day == fresh_timing.day)?;
+            match (stored_timing.threads, fresh_timing.threads) {
+                (Some(a), Some(b)) if a != b => Some((fresh_timing.day, a, b)),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Returns the deltas that are slower than the stored timing by more than `threshold` percent,
+/// or that no longer complete.
+pub fn regressions(deltas: &[Delta], threshold: f64) -> Vec<&Delta> {
+    deltas
+        .iter()
+        .filter(|d| d.percent().is_none_or(|percent| percent > threshold))
+        .collect()
+}
+
+pub fn print_comparison(deltas: &[Delta], mismatches: &[(Day, u64, u64)]) {
+    println!("\n{ANSI_BOLD}Compared to stored timings:{ANSI_RESET}");
+
+    if deltas.is_empty() {
+        println!("No stored timings to compare with.");
+    }
+
+    for delta in deltas {
+        println!("{}", delta.format());
+    }
+
+    for (day, stored, fresh) in mismatches {
+        println!("Note: day {day} was stored with {stored} threads, but ran on {fresh} threads.");
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{compare, regressions, thread_mismatches, Delta};
+    use crate::{
+        day,
+        template::{
+            report::{Stage, Status},
+            timings::{StageTiming, Timing, Timings},
+            Day,
+        },
+    };
+
+    fn timing(day: Day, part_1: Option<&str>, part_2: Option<&str>) -> Timing {
+        Timing {
+            part_1: StageTiming {
+                time: part_1.map(Into::into),
+                ..StageTiming::default()
+            },
+            part_2: StageTiming {
+                time: part_2.map(Into::into),
+                ..StageTiming::default()
+            },
+            ..Timing::new(day)
+        }
+    }
+
+    #[test]
+    fn compares_timed_stages() {
+        let stored = Timings {
+            data: vec![
+                timing(day!(1), Some("10.0ms"), Some("20.0ms")),
+                timing(day!(2), Some("1.0µs"), None),
+            ],
+        };
+        let fresh = Timings {
+            data: vec![
+                timing(day!(1), Some("12.0ms"), Some("10.0ms")),
+                timing(day!(2), Some("1.0µs"), Some("5.0µs")),
+                timing(day!(3), Some("1.0ms"), None),
+            ],
+        };
+
+        let deltas = compare(&stored, &fresh);
+
+        assert_eq!(deltas.len(), 3);
+        assert_eq!(deltas[0].day, day!(1));
+        assert_eq!(deltas[0].stage, Stage::Part(1));
+        assert!((deltas[0].percent().unwrap() - 20.0).abs() < 1e-9);
+        assert!((deltas[1].percent().unwrap() + 50.0).abs() < 1e-9);
+        assert_eq!(deltas[2].percent(), Some(0.0));
+
+        let regressed = regressions(&deltas, 10.0);
+        assert_eq!(regressed, vec![&deltas[0]]);
+        assert!(regressions(&deltas, 25.0).is_empty());
+    }
+
+    #[test]
+    fn reports_stages_that_no_longer_complete() {
+        let stored = Timings {
+            data: vec![timing(day!(1), Some("10.0ms"), Some("20.0ms"))],
+        };
+        let mut fresh = Timings {
+            data: vec![timing(day!(1), Some("10.0ms"), None)],
+        };
+        fresh.data[0].part_2.status = Some(Status::Panicked);
+
+        let deltas = compare(&stored, &fresh);
+
+        assert_eq!(deltas.len(), 2);
+        assert_eq!(deltas[1].stage, Stage::Part(2));
+        assert_eq!(deltas[1].fresh, Err(Status::Panicked));
+        assert_eq!(deltas[1].percent(), None);
+        assert_eq!(regressions(&deltas, 10.0), vec![&deltas[1]]);
+
+        // stages that were not timed before are not compared.
+        let stored = Timings {
+            data: vec![timing(day!(1), Some("10.0ms"), None)],
+        };
+        assert_eq!(compare(&stored, &fresh).len(), 1);
+    }
+
+    #[test]
+    fn detects_thread_mismatches() {
+        let mut stored = Timings {
+            data: vec![timing(day!(1), None, None), timing(day!(2), None, None)],
+        };
+        let mut fresh = stored.clone();
+
+        stored.data[0].threads = Some(8);
+        stored.data[1].threads = Some(8);
+        fresh.data[0].threads = Some(2);
+        fresh.data[1].threads = Some(8);
+
+        assert_eq!(thread_mismatches(&stored, &fresh), vec![(day!(1), 8, 2)]);
+    }
+
+    #[test]
+    fn formats_deltas() {
+        let delta = Delta {
+            day: day!(4),
+            stage: Stage::Part(2),
+            stored_nanos: 2_000_000.0,
+            fresh: Ok(1_500_000.0),
+        };
+        assert_eq!(
+            delta.format(),
+            "Day 04 Part 2: 2.0ms → 1.5ms \x1b[32m-500.0µs (-25.0%)\x1b[0m"
+        );
+
+        let delta = Delta {
+            fresh: Err(Status::TimedOut),
+            ..delta
+        };
+        assert_eq!(
+            delta.format(),
+            "Day 04 Part 2: 2.0ms → \x1b[31m✖ timed out\x1b[0m"
+        );
+    }
+}
@@ -0,0 +1,159 @@
+/// Measures the CPU time a solution stage consumes, to compare it with the elapsed wall-clock time.
+/// Parallel solutions can use several CPUs at once, so their CPU time can be a multiple of their wall time.
+use std::collections::HashMap;
+use std::time::Duration;
+
+use tinyjson::JsonValue;
+
+use crate::template::stats::nanos_to_duration;
+
+/// CPU time (user + system, summed over all threads of the process) spent in a single solution stage.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CpuTime {
+    /// CPU time per execution, in nanoseconds.
+    pub nanos: f64,
+    /// CPU time divided by wall-clock time. Values above 1 mean that the stage used several CPUs in parallel.
+    pub ratio: f64,
+}
+
+impl CpuTime {
+    /// Computes the CPU time of a single execution from `cpu` and `wall`, both measured across `runs` executions.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn from_measurement(cpu: Duration, wall: Duration, runs: u64) -> Self {
+        let cpu_nanos = cpu.as_nanos() as f64;
+        let wall_nanos = wall.as_nanos() as f64;
+
+        CpuTime {
+            nanos: cpu_nanos / runs.max(1) as f64,
+            ratio: if wall_nanos > 0.0 {
+                cpu_nanos / wall_nanos
+            } else {
+                0.0
+            },
+        }
+    }
+
+    pub fn summary(&self) -> String {
+        format!(
+            "cpu {:.1?} ×{:.2}",
+            nanos_to_duration(self.nanos),
+            self.ratio
+        )
+    }
+}
+
+/// Returns the CPU time the process has consumed so far, or `None` if it cannot be measured on this platform.
+#[cfg(unix)]
+pub fn process_time() -> Option<Duration> {
+    let mut ts = libc::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+
+    // SAFETY: `ts` is a valid, writable `timespec`.
+    let res = unsafe { libc::clock_gettime(libc::CLOCK_PROCESS_CPUTIME_ID, &mut ts) };
+
+    if res != 0 {
+        return None;
+    }
+
+    Some(Duration::new(
+        u64::try_from(ts.tv_sec).ok()?,
+        u32::try_from(ts.tv_nsec).ok()?,
+    ))
+}
+
+#[cfg(not(unix))]
+pub fn process_time() -> Option<Duration> {
+    None
+}
+
+/// Tracks the CPU time consumed between its creation and a call to [`CpuTimer::elapsed`].
+pub struct CpuTimer(Option<Duration>);
+
+impl CpuTimer {
+    pub fn start() -> Self {
+        CpuTimer(process_time())
+    }
+
+    pub fn elapsed(&self) -> Option<Duration> {
+        Some(process_time()?.saturating_sub(self.0?))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&CpuTime> for JsonValue {
+    fn from(value: &CpuTime) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("nanos".into(), JsonValue::Number(value.nanos));
+        map.insert("ratio".into(), JsonValue::Number(value.ratio));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for CpuTime {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected cpu to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected cpu.{key} to be a number."))
+        };
+
+        Ok(CpuTime {
+            nanos: number("nanos")?,
+            ratio: number("ratio")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::hint::black_box;
+    use std::time::Duration;
+
+    use tinyjson::JsonValue;
+
+    use super::{CpuTime, CpuTimer};
+
+    #[test]
+    fn computes_cpu_time_per_run() {
+        let cpu =
+            CpuTime::from_measurement(Duration::from_millis(40), Duration::from_millis(10), 4);
+        assert_eq!(cpu.nanos, 10_000_000.0);
+        assert_eq!(cpu.ratio, 4.0);
+        assert_eq!(cpu.summary(), "cpu 10.0ms ×4.00");
+
+        let cpu = CpuTime::from_measurement(Duration::ZERO, Duration::ZERO, 0);
+        assert_eq!(cpu, CpuTime::default());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn measures_busy_work() {
+        let timer = CpuTimer::start();
+        let sum: u64 = (0..1_000_000_u64).map(black_box).sum();
+        black_box(sum);
+        assert!(timer.elapsed().unwrap() > Duration::ZERO);
+    }
+
+    #[test]
+    fn roundtrips_cpu_time() {
+        let cpu = CpuTime {
+            nanos: 1500.0,
+            ratio: 3.5,
+        };
+        let json = JsonValue::from(&cpu);
+        assert_eq!(CpuTime::try_from(&json).unwrap(), cpu);
+    }
+}
@@ -134,6 +134,71 @@ impl Iterator for AllDays {
 
 /* -------------------------------------------------------------------------- */
 
+/// An inclusive range of days, parsed like a Rust range: `3..=12`, `3..13`, or a single day.
+///
+/// ```
+/// # use advent_of_code::template::DayRange;
+/// let range: DayRange = "3..=12".parse().unwrap();
+/// assert_eq!(range.days().count(), 10);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DayRange {
+    start: Day,
+    end: Day,
+}
+
+impl DayRange {
+    /// Every day of advent.
+    pub fn all() -> Self {
+        Self {
+            start: Day(1),
+            end: Day(25),
+        }
+    }
+
+    pub fn days(self) -> impl Iterator<Item = Day> {
+        all_days().filter(move |day| (self.start..=self.end).contains(day))
+    }
+}
+
+impl FromStr for DayRange {
+    type Err = DayRangeFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = if let Some((start, end)) = s.split_once("..=") {
+            (start.parse(), end.parse())
+        } else if let Some((start, end)) = s.split_once("..") {
+            let end = end
+                .parse::<u8>()
+                .ok()
+                .and_then(|end| Day::new(end.checked_sub(1)?))
+                .ok_or(DayFromStrError);
+            (start.parse(), end)
+        } else {
+            (s.parse(), s.parse())
+        };
+
+        match (start, end) {
+            (Ok(start), Ok(end)) if start <= end => Ok(Self { start, end }),
+            _ => Err(DayRangeFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`DayRange`].
+#[derive(Debug)]
+pub struct DayRangeFromStrError;
+
+impl Error for DayRangeFromStrError {}
+
+impl Display for DayRangeFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a range of days between 1 and 25, e.g. `3..=12` or `3..13`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 /// Creates a [`Day`] value in a const context.
 #[macro_export]
 macro_rules! day {
@@ -154,7 +219,7 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use super::{all_days, Day, DayRange};
 
     #[test]
     fn all_days_iterator() {
@@ -187,6 +252,27 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn parses_day_ranges() {
+        let days = |range: &str| {
+            range
+                .parse::<DayRange>()
+                .map(|range| range.days().map(Day::into_inner).collect::<Vec<_>>())
+        };
+
+        assert_eq!(days("3..=6").unwrap(), vec![3, 4, 5, 6]);
+        assert_eq!(days("3..6").unwrap(), vec![3, 4, 5]);
+        assert_eq!(days("25").unwrap(), vec![25]);
+        assert_eq!(days("1..26").unwrap().len(), 25);
+        assert_eq!(DayRange::all().days().count(), 25);
+
+        for invalid in [
+            "", "0..=3", "5..=3", "5..5", "1..=26", "1..1", "a..b", "3-6",
+        ] {
+            assert!(days(invalid).is_err(), "{invalid}");
+        }
+    }
 }
 
 /* -------------------------------------------------------------------------- */
@@ -0,0 +1,393 @@
+/// Reads the example manifest of a day, which lists example inputs along with their expected answers.
+/// The manifest lives next to the examples in `data/examples/<day>.json`:
+///
+/// ```json
+/// { "examples": [{ "file": "06.txt", "part_1": 41, "part_2": 6 }, { "file": "06-loop.txt", "part_2": "1" }] }
+/// ```
+use std::{
+    collections::HashMap,
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::runner::{catch_panic, Answer, Outcome};
+use crate::template::Day;
+
+/// Numbers above this cannot be represented exactly in JSON and have to be written as strings.
+const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+
+/// An example input and the answers it should produce.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Example {
+    /// File name, relative to `data/examples`.
+    pub file: String,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl Example {
+    pub fn answer(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Manifest {
+    pub examples: Vec<Example>,
+}
+
+impl Manifest {
+    pub fn path(day: Day) -> PathBuf {
+        examples_dir().join(format!("{day}.json"))
+    }
+
+    /// Reads the manifest of a day. Returns `None` if the day has no manifest.
+    pub fn read(day: Day) -> Result<Option<Self>, String> {
+        let path = Manifest::path(day);
+
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(format!("Could not read \"{}\": {e}", path.display())),
+        };
+
+        let json: JsonValue = content
+            .parse()
+            .map_err(|e| format!("Invalid JSON in \"{}\": {e}", path.display()))?;
+
+        Manifest::try_from(&json)
+            .map(Some)
+            .map_err(|e| format!("Invalid example manifest \"{}\": {e}", path.display()))
+    }
+
+    /// Writes the manifest to `data/examples/<day>.json`.
+    pub fn store(&self, day: Day) -> Result<(), String> {
+        let path = Manifest::path(day);
+        let json = JsonValue::from(self);
+
+        fs::File::create(&path)
+            .and_then(|mut file| json.format_to(&mut file))
+            .map_err(|e| format!("Could not write \"{}\": {e}", path.display()))
+    }
+
+    /// Sets the answer of an example, adding the example if the manifest does not list it yet.
+    pub fn set(&mut self, file: &str, part: u8, answer: &str) {
+        let index = match self.examples.iter().position(|e| e.file == file) {
+            Some(index) => index,
+            None => {
+                self.examples.push(Example {
+                    file: file.into(),
+                    part_1: None,
+                    part_2: None,
+                });
+                self.examples.len() - 1
+            }
+        };
+
+        let example = &mut self.examples[index];
+        match part {
+            1 => example.part_1 = Some(answer.into()),
+            2 => example.part_2 = Some(answer.into()),
+            _ => {}
+        }
+    }
+
+    /// Returns the example for the file with the given name.
+    pub fn find(&self, file: &str) -> Option<&Example> {
+        self.examples.iter().find(|example| example.file == file)
+    }
+}
+
+fn examples_dir() -> PathBuf {
+    env::current_dir()
+        .unwrap_or_default()
+        .join("data")
+        .join("examples")
+}
+
+/// Runs `func` on every example of the day's manifest that lists an answer for `part`, and panics with a list of all examples that produced a different answer.
+/// An example that panics is reported like a wrong answer, the remaining examples still run.
+/// Used by the tests that [`example_tests!`](crate::example_tests) generates.
+///
+/// # Panics
+///
+/// Panics if the manifest is missing or invalid, or if any example produces a wrong answer or panics.
+#[track_caller]
+pub fn check<R: Answer>(day: Day, part: u8, func: impl Fn(&str) -> R) {
+    let manifest = match Manifest::read(day) {
+        Ok(Some(manifest)) => manifest,
+        Ok(None) => panic!(
+            "No example manifest found at \"{}\".",
+            Manifest::path(day).display()
+        ),
+        Err(e) => panic!("{e}"),
+    };
+
+    let failures = failures(&manifest, &examples_dir(), part, func);
+
+    assert!(
+        failures.is_empty(),
+        "Part {part} failed {} example(s):\n{}",
+        failures.len(),
+        failures.join("\n")
+    );
+}
+
+/// Runs `func` on the examples in `dir` that list an answer for `part` and describes every example that failed, prefixed with its file name.
+fn failures<R: Answer>(
+    manifest: &Manifest,
+    dir: &Path,
+    part: u8,
+    func: impl Fn(&str) -> R,
+) -> Vec<String> {
+    let mut failures = vec![];
+
+    for example in &manifest.examples {
+        let Some(expected) = example.answer(part) else {
+            continue;
+        };
+
+        let path = dir.join(&example.file);
+        let input = match fs::read_to_string(&path) {
+            Ok(input) => input,
+            Err(e) => {
+                failures.push(format!("{}: could not read file: {e}", example.file));
+                continue;
+            }
+        };
+
+        match catch_panic(|| func(&input).outcome()) {
+            Ok(outcome) => {
+                if let Some(mismatch) = mismatch(&outcome, expected) {
+                    failures.push(format!("{}: {mismatch}", example.file));
+                }
+            }
+            Err(message) => failures.push(format!("{}: panicked: {message}", example.file)),
+        }
+    }
+
+    failures
+}
+
+/// Describes how `outcome` differs from the expected answer, `None` if it matches.
+pub fn mismatch(outcome: &Outcome, expected: &str) -> Option<String> {
+    match outcome {
+        Outcome::Answer(answer) if answer == expected => None,
+        Outcome::Answer(answer) => Some(format!("expected {expected}, got {answer}")),
+        Outcome::NoAnswer => Some(format!("expected {expected}, got no answer")),
+        Outcome::Error(e) => Some(format!("expected {expected}, got error: {e}")),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Manifest> for JsonValue {
+    fn from(value: &Manifest) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "examples".into(),
+            JsonValue::Array(value.examples.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl From<&Example> for JsonValue {
+    /// Writes answers that are safe integers as numbers, like in hand-written manifests.
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Example) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("file".into(), JsonValue::String(value.file.clone()));
+
+        for (key, answer) in [("part_1", &value.part_1), ("part_2", &value.part_2)] {
+            let json = match answer {
+                None => continue,
+                Some(answer) => match answer.parse::<i64>() {
+                    Ok(n) if (n as f64).abs() <= MAX_SAFE_INTEGER && n.to_string() == *answer => {
+                        JsonValue::Number(n as f64)
+                    }
+                    _ => JsonValue::String(answer.clone()),
+                },
+            };
+            map.insert(key.into(), json);
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Manifest {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let examples = value
+            .get::<HashMap<String, JsonValue>>()
+            .and_then(|json| json.get("examples"))
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected manifest.examples to be an array.")?;
+
+        Ok(Manifest {
+            examples: examples
+                .iter()
+                .map(Example::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl TryFrom<&JsonValue> for Example {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected example to be a JSON object.")?;
+
+        let file = json
+            .get("file")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected example.file to be a string.")?;
+
+        Ok(Example {
+            file: file.clone(),
+            part_1: optional_answer(json, "example.part_1")?,
+            part_2: optional_answer(json, "example.part_2")?,
+        })
+    }
+}
+
+/// Reads an answer that is either a string or an integer.
+/// `path` is the key, prefixed with its parents for error messages, e.g. `example.part_1`.
+#[allow(clippy::cast_possible_truncation)]
+pub(crate) fn optional_answer(
+    json: &HashMap<String, JsonValue>,
+    path: &str,
+) -> Result<Option<String>, String> {
+    let key = path.rsplit('.').next().unwrap_or(path);
+
+    match json.get(key) {
+        None => Ok(None),
+        Some(v) if v.is_null() => Ok(None),
+        Some(JsonValue::String(s)) => Ok(Some(s.clone())),
+        Some(JsonValue::Number(n)) if n.fract() == 0.0 && n.abs() <= MAX_SAFE_INTEGER => {
+            Ok(Some((*n as i64).to_string()))
+        }
+        Some(JsonValue::Number(_)) => Err(format!(
+            "Expected {path} to be an integer, write large or non-integer answers as strings."
+        )),
+        Some(_) => Err(format!("Expected {path} to be null, a string or a number.")),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs, process};
+
+    use tinyjson::JsonValue;
+
+    use super::{failures, mismatch, Example, Manifest};
+    use crate::template::runner::Outcome;
+
+    fn parse(json: &str) -> Result<Manifest, String> {
+        Manifest::try_from(&json.parse::<JsonValue>().unwrap())
+    }
+
+    #[test]
+    fn parses_manifest() {
+        let manifest = parse(
+            r#"{ "examples": [
+                { "file": "06.txt", "part_1": 41, "part_2": "6" },
+                { "file": "06-2.txt", "part_1": null, "part_2": -3 }
+            ] }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            manifest.examples[0],
+            Example {
+                file: "06.txt".into(),
+                part_1: Some("41".into()),
+                part_2: Some("6".into()),
+            }
+        );
+        assert_eq!(manifest.examples[1].answer(1), None);
+        assert_eq!(manifest.examples[1].answer(2), Some("-3"));
+        assert_eq!(manifest.find("06-2.txt"), Some(&manifest.examples[1]));
+        assert_eq!(manifest.find("07.txt"), None);
+    }
+
+    #[test]
+    fn rejects_invalid_manifests() {
+        assert!(parse(r#"{ "examples": {} }"#).is_err());
+        assert!(parse(r#"{ "examples": [{ "part_1": 1 }] }"#).is_err());
+        assert!(parse(r#"{ "examples": [{ "file": "a.txt", "part_1": 1.5 }] }"#).is_err());
+        assert!(parse(r#"{ "examples": [{ "file": "a.txt", "part_1": 1e17 }] }"#).is_err());
+        assert!(parse(r#"{ "examples": [{ "file": "a.txt", "part_2": [] }] }"#).is_err());
+    }
+
+    #[test]
+    fn roundtrips_manifests() {
+        let mut manifest = Manifest::default();
+        manifest.set("06.txt", 1, "41");
+        manifest.set("06-2.txt", 2, "9007199254740993");
+        manifest.set("06.txt", 2, "CMZ");
+
+        let json = JsonValue::from(&manifest);
+        assert_eq!(Manifest::try_from(&json).unwrap(), manifest);
+        assert!(json.stringify().unwrap().contains(r#""part_1":41"#));
+        assert_eq!(manifest.examples.len(), 2);
+    }
+
+    #[test]
+    fn describes_mismatches() {
+        assert_eq!(mismatch(&Outcome::Answer("6".into()), "6"), None);
+        assert_eq!(
+            mismatch(&Outcome::Answer("7".into()), "6"),
+            Some("expected 6, got 7".into())
+        );
+        assert_eq!(
+            mismatch(&Outcome::NoAnswer, "6"),
+            Some("expected 6, got no answer".into())
+        );
+        assert_eq!(
+            mismatch(&Outcome::Error("bad input".into()), "6"),
+            Some("expected 6, got error: bad input".into())
+        );
+    }
+
+    #[test]
+    fn reports_every_failing_example() {
+        let dir = env::temp_dir().join(format!("aoc-examples-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for (file, input) in [("ok.txt", "1"), ("wrong.txt", "2"), ("panics.txt", "x")] {
+            fs::write(dir.join(file), input).unwrap();
+        }
+
+        let mut manifest = Manifest::default();
+        manifest.set("ok.txt", 1, "2");
+        manifest.set("wrong.txt", 1, "5");
+        manifest.set("panics.txt", 1, "1");
+        manifest.set("missing.txt", 1, "1");
+
+        let failures = failures(&manifest, &dir, 1, |input| {
+            Some(input.parse::<u32>().unwrap() * 2)
+        });
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(failures.len(), 3);
+        assert_eq!(failures[0], "wrong.txt: expected 5, got 4");
+        assert!(failures[1].starts_with("panics.txt: panicked: "));
+        assert!(failures[2].starts_with("missing.txt: could not read file: "));
+    }
+}
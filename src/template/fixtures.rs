@@ -0,0 +1,305 @@
+/// A backend that serves Advent of Code from a local directory, for working offline and for tests.
+/// The directory mirrors what the website knows about a user:
+///
+/// - `inputs/01.txt`: the input of a day.
+/// - `puzzles/01.html`: the puzzle page of a day, with one `<article>` per part. Part two unlocks once part one is solved.
+/// - `answers.json`: the correct answers, in the format of `data/answers/<year>.json`.
+/// - `leaderboards/<id>.json`: the JSON of a private leaderboard.
+/// - `submissions.json`: written by the backend, the answers submitted to it in the format of `data/submissions/<year>.json`.
+use std::{fs, io, path::PathBuf};
+
+use tinyjson::JsonValue;
+
+use crate::template::answers::Answers;
+use crate::template::aoc_client::{AocClientError, Fetched, Puzzle, SubmitResponse, Validators};
+use crate::template::backend::AocBackend;
+use crate::template::markdown;
+use crate::template::submissions::{History, Verdict};
+use crate::template::Day;
+
+#[derive(Clone)]
+pub struct Fixtures {
+    dir: PathBuf,
+}
+
+impl Fixtures {
+    pub fn new(dir: PathBuf) -> Self {
+        Fixtures { dir }
+    }
+
+    /// Reads a fixture, responding with a 404 like the website does if it is missing.
+    fn read(&self, path: &str) -> Result<String, AocClientError> {
+        let path = self.dir.join(path);
+
+        fs::read_to_string(&path).map_err(|e| match e.kind() {
+            io::ErrorKind::NotFound => AocClientError::Status {
+                status: 404,
+                body: format!("{} does not exist.", path.display()),
+            },
+            _ => AocClientError::Backend(format!("Could not read \"{}\": {e}", path.display())),
+        })
+    }
+
+    fn answers(&self) -> Result<Answers, AocClientError> {
+        match self.read("answers.json") {
+            Ok(json) => Answers::try_from(json).map_err(AocClientError::Backend),
+            Err(AocClientError::Status { .. }) => Ok(Answers::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn history(&self) -> Result<History, AocClientError> {
+        match self.read("submissions.json") {
+            Ok(json) => History::try_from(json).map_err(AocClientError::Backend),
+            Err(AocClientError::Status { .. }) => Ok(History::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn store_history(&self, history: &History) -> Result<(), AocClientError> {
+        let path = self.dir.join("submissions.json");
+        fs::File::create(&path)
+            .and_then(|mut file| JsonValue::from(history).format_to(&mut file))
+            .map_err(|e| {
+                AocClientError::Backend(format!("Could not write \"{}\": {e}", path.display()))
+            })
+    }
+
+    /// The puzzle page of a day with the parts that are unlocked, like the website shows it.
+    pub fn puzzle_page(&self, day: Day) -> Result<String, AocClientError> {
+        let html = self.read(&format!("puzzles/{day}.html"))?;
+        let unlocked = usize::from(Fixtures::solved_parts(&self.history()?, day)) + 1;
+
+        let articles: String = markdown::articles(&html)
+            .iter()
+            .take(unlocked)
+            .map(|article| format!("<article class=\"day-desc\">{article}</article>\n"))
+            .collect();
+        Ok(format!("<main>\n{articles}</main>"))
+    }
+
+    /// Judges a submitted answer and records it, returning the response page of the website.
+    pub fn judge_submission(
+        &self,
+        day: Day,
+        part: u8,
+        answer: &str,
+    ) -> Result<String, AocClientError> {
+        let mut history = self.history()?;
+        let html = judge(
+            Fixtures::solved_parts(&history, day),
+            part,
+            answer,
+            self.answers()?.get(day, part),
+        );
+
+        if let Some(verdict) = Verdict::from_response(&SubmitResponse::from_html(&html).message) {
+            history.record(day, part, answer, verdict);
+            self.store_history(&history)?;
+        }
+
+        Ok(html)
+    }
+
+    /// The number of parts of a day that were solved by a correct submission.
+    fn solved_parts(history: &History, day: Day) -> u8 {
+        let solved = |part: u8| {
+            history
+                .data
+                .iter()
+                .any(|s| s.day == day && s.part == part && s.verdict == Verdict::Correct)
+        };
+
+        match (solved(1), solved(2)) {
+            (true, true) => 2,
+            (true, false) => 1,
+            _ => 0,
+        }
+    }
+}
+
+impl AocBackend for Fixtures {
+    fn input(&self, day: Day, _: &Validators) -> Result<Fetched<String>, AocClientError> {
+        self.read(&format!("inputs/{day}.txt"))
+            .map(|input| Fetched::Modified(input, Validators::default()))
+    }
+
+    fn puzzle(&self, day: Day, _: &Validators) -> Result<Fetched<Puzzle>, AocClientError> {
+        let page = self.puzzle_page(day)?;
+        let puzzle = Puzzle::from_articles(&markdown::articles(&page))?;
+        Ok(Fetched::Modified(puzzle, Validators::default()))
+    }
+
+    fn leaderboard(&self, id: u64) -> Result<String, AocClientError> {
+        self.read(&format!("leaderboards/{id}.json"))
+    }
+
+    fn submit(&self, day: Day, part: u8, answer: &str) -> Result<SubmitResponse, AocClientError> {
+        self.judge_submission(day, part, answer)
+            .map(|html| SubmitResponse::from_html(&html))
+    }
+
+    fn is_remote(&self) -> bool {
+        false
+    }
+}
+
+/// Responds to a submission like the website does. Answers without a known correct answer are wrong.
+pub fn judge(solved_parts: u8, part: u8, answer: &str, correct: Option<&str>) -> String {
+    let message = if part != solved_parts + 1 {
+        "You don't seem to be solving the right level.  Did you already complete it?".into()
+    } else if correct == Some(answer.trim()) {
+        "That's the right answer!  You are one gold star closer to finding the Chief Historian."
+            .into()
+    } else {
+        let hint = match (answer.trim().parse::<i64>(), correct.map(str::parse::<i64>)) {
+            (Ok(answer), Some(Ok(correct))) if answer > correct => "; your answer is too high",
+            (Ok(answer), Some(Ok(correct))) if answer < correct => "; your answer is too low",
+            _ => "",
+        };
+        format!("That's not the right answer{hint}.  If you're stuck, make sure you're using the full input data.")
+    };
+
+    format!("<main>\n<article><p>{message}</p></article>\n</main>")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+pub(crate) mod tests {
+    use std::{env, fs, path::PathBuf, process};
+
+    use super::Fixtures;
+    use crate::day;
+    use crate::template::aoc_client::{AocClientError, Fetched, SubmitOutcome, Validators};
+    use crate::template::backend::AocBackend;
+    use crate::template::cache::Cache;
+    use crate::template::submissions::{History, Verdict};
+    use crate::template::year_from_env;
+
+    pub(crate) const PUZZLE: &str = "<main>
+<article class=\"day-desc\"><h2>--- Day 1: Historian Hysteria ---</h2><p>For example:</p><pre><code>3   4
+4   3
+</code></pre><p>In the example above, the total distance is <code><em>3</em></code>.</p></article>
+<article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2><p>What is their similarity score?</p></article>
+</main>";
+
+    /// A fresh directory in the temporary directory, with a `fixtures` directory for day 1 and an empty `data` directory.
+    /// The directory is removed when the sandbox is dropped, also if the test fails.
+    pub(crate) struct Sandbox {
+        pub(crate) root: PathBuf,
+        pub(crate) fixtures: Fixtures,
+        pub(crate) data: PathBuf,
+    }
+
+    impl Sandbox {
+        pub(crate) fn new(name: &str) -> Self {
+            let root = env::temp_dir().join(format!("aoc-fixtures-{name}-{}", process::id()));
+            let _ = fs::remove_dir_all(&root);
+
+            for dir in [
+                "fixtures/inputs",
+                "fixtures/puzzles",
+                "fixtures/leaderboards",
+                "data/inputs",
+                "data/puzzles",
+            ] {
+                fs::create_dir_all(root.join(dir)).unwrap();
+            }
+
+            let files = [
+                ("fixtures/inputs/01.txt", "1   2\n"),
+                ("fixtures/puzzles/01.html", PUZZLE),
+                (
+                    "fixtures/answers.json",
+                    r#"{ "data": [{ "day": "01", "part_1": 11, "part_2": "CMZ" }] }"#,
+                ),
+                (
+                    "fixtures/leaderboards/7.json",
+                    r#"{ "members": { "7": { "id": 7, "name": "Ada", "stars": 1, "local_score": 5,
+                    "completion_day_level": { "1": { "1": { "get_star_ts": 1733029500 } } } } } }"#,
+                ),
+            ];
+            for (path, content) in files {
+                fs::write(root.join(path), content).unwrap();
+            }
+
+            Sandbox {
+                fixtures: Fixtures::new(root.join("fixtures")),
+                data: root.join("data"),
+                root,
+            }
+        }
+
+        /// Opens the cache of the data directory, downloading from the fixtures.
+        pub(crate) fn cache(&self) -> Cache<'_> {
+            Cache::open_in(&self.fixtures, &self.data, year_from_env().ok(), false).unwrap()
+        }
+
+        /// Submits the correct answer to part one of day 1 and records it in the submission history of the data directory, like `cargo solve 1 --submit 1` does.
+        pub(crate) fn solve_part_one(&self) {
+            self.fixtures.submit(day!(1), 1, "11").unwrap();
+
+            let year = year_from_env().unwrap();
+            let mut history = History::read_in(&self.data, year).unwrap();
+            history.record(day!(1), 1, "11", Verdict::Correct);
+            history.store_in(&self.data, year).unwrap();
+        }
+    }
+
+    impl Drop for Sandbox {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.root);
+        }
+    }
+
+    #[test]
+    fn serves_fixtures() {
+        let sandbox = Sandbox::new("serve");
+        let fixtures = &sandbox.fixtures;
+
+        assert_eq!(
+            fixtures.input(day!(1), &Validators::default()).unwrap(),
+            Fetched::Modified("1   2\n".into(), Validators::default())
+        );
+        assert!(matches!(
+            fixtures.input(day!(2), &Validators::default()),
+            Err(AocClientError::Status { status: 404, .. })
+        ));
+        assert!(fixtures.leaderboard(7).unwrap().contains("Ada"));
+
+        let Fetched::Modified(puzzle, _) =
+            fixtures.puzzle(day!(1), &Validators::default()).unwrap()
+        else {
+            panic!("expected a puzzle");
+        };
+        assert_eq!(puzzle.parts, 1);
+        assert!(puzzle
+            .markdown
+            .starts_with("## --- Day 1: Historian Hysteria ---"));
+    }
+
+    #[test]
+    fn judges_submissions() {
+        let sandbox = Sandbox::new("judge");
+        let fixtures = &sandbox.fixtures;
+
+        let outcome =
+            |part: u8, answer: &str| fixtures.submit(day!(1), part, answer).unwrap().outcome;
+
+        assert_eq!(outcome(2, "CMZ"), SubmitOutcome::WrongLevel);
+        assert_eq!(outcome(1, "12"), SubmitOutcome::Judged(Verdict::TooHigh));
+        assert_eq!(outcome(1, "10"), SubmitOutcome::Judged(Verdict::TooLow));
+        assert_eq!(outcome(1, "11"), SubmitOutcome::Judged(Verdict::Correct));
+        assert_eq!(outcome(1, "11"), SubmitOutcome::WrongLevel);
+        assert_eq!(outcome(2, "ABC"), SubmitOutcome::Judged(Verdict::Wrong));
+
+        let Fetched::Modified(puzzle, _) =
+            fixtures.puzzle(day!(1), &Validators::default()).unwrap()
+        else {
+            panic!("expected a puzzle");
+        };
+        assert_eq!(puzzle.parts, 2);
+        assert_eq!(fixtures.history().unwrap().data.len(), 4);
+    }
+}
@@ -0,0 +1,314 @@
+/// Selects the input a solution runs on.
+/// By default solutions read `data/inputs/<day>.txt`, `cargo solve` can point them to an example, another file or stdin instead.
+/// A missing or empty puzzle input can be downloaded through the configured backend before the solution runs.
+use std::{
+    env,
+    fmt::{self, Display},
+    fs,
+    io::{self, Read},
+    path::PathBuf,
+};
+
+use pico_args::Arguments;
+
+use crate::template::backend;
+use crate::template::cache::{Cache, Resource};
+use crate::template::session;
+use crate::template::{confirm, Day};
+
+/// What to do when the puzzle input is missing or empty, e.g. right after `cargo scaffold`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MissingInput {
+    /// Stop with a message that explains how to get the input.
+    Fail,
+    /// Ask whether to download the input, if there is a terminal to ask on.
+    Ask,
+    Download,
+}
+
+/// Where a solution reads its input from.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// The puzzle input in `data/inputs/<day>.txt`.
+    #[default]
+    Puzzle,
+    /// The example in `data/examples/<day>.txt`, or `data/examples/<day>-<part>.txt` if a part is given.
+    Example(Option<u8>),
+    /// An arbitrary file.
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    /// Reads `--example`, `--example-part <n>` and `--input <path>`, where a path of `-` reads from stdin.
+    pub fn from_args(args: &mut Arguments) -> Result<Self, pico_args::Error> {
+        let example = args.contains("--example");
+        let example_part: Option<u8> = args.opt_value_from_str("--example-part")?;
+        let input =
+            args.opt_value_from_os_str("--input", |s| Ok::<_, pico_args::Error>(PathBuf::from(s)))?;
+
+        let source = match (example || example_part.is_some(), input) {
+            (false, None) => InputSource::Puzzle,
+            (true, None) => InputSource::Example(example_part),
+            (false, Some(path)) if path.as_os_str() == "-" => InputSource::Stdin,
+            (false, Some(path)) => InputSource::File(path),
+            (true, Some(_)) => {
+                return Err(pico_args::Error::ArgumentParsingFailed {
+                    cause: "--input cannot be combined with --example or --example-part.".into(),
+                })
+            }
+        };
+
+        Ok(source)
+    }
+
+    /// Formats the source as arguments for a solution binary, the inverse of [`InputSource::from_args`].
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            InputSource::Puzzle => vec![],
+            InputSource::Example(None) => vec!["--example".into()],
+            InputSource::Example(Some(part)) => vec!["--example-part".into(), part.to_string()],
+            InputSource::File(path) => vec!["--input".into(), path.to_string_lossy().into()],
+            InputSource::Stdin => vec!["--input".into(), "-".into()],
+        }
+    }
+
+    /// Returns the path of the file the input is read from, `None` for stdin.
+    pub fn path(&self, day: Day) -> Option<PathBuf> {
+        let data = |folder: &str, name: String| {
+            env::current_dir()
+                .unwrap_or_default()
+                .join("data")
+                .join(folder)
+                .join(name)
+        };
+
+        match self {
+            InputSource::Puzzle => Some(data("inputs", format!("{day}.txt"))),
+            InputSource::Example(None) => Some(data("examples", format!("{day}.txt"))),
+            InputSource::Example(Some(part)) => Some(data("examples", format!("{day}-{part}.txt"))),
+            InputSource::File(path) => Some(path.clone()),
+            InputSource::Stdin => None,
+        }
+    }
+
+    /// Like [`InputSource::read`], but handles a missing or empty puzzle input as `missing` says.
+    /// Other sources are read as they are.
+    pub fn read_or_download(&self, day: Day, missing: MissingInput) -> Result<String, String> {
+        let input = self.read(day);
+        if *self != InputSource::Puzzle || input.as_ref().is_ok_and(|i| !i.trim().is_empty()) {
+            return input;
+        }
+
+        let state = if input.is_ok() { "empty" } else { "missing" };
+        let account = session::account_from_env()?;
+
+        handle_missing(day, state, account.as_deref(), missing, download_input)
+    }
+
+    pub fn read(&self, day: Day) -> Result<String, String> {
+        match self.path(day) {
+            Some(path) => fs::read_to_string(&path)
+                .map_err(|e| format!("Could not read \"{}\": {e}", path.display())),
+            None => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(|e| format!("Could not read input from stdin: {e}"))?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+/// Explains how to get a puzzle input that is missing or empty (`state`), or downloads it with `download` as `missing` says.
+fn handle_missing(
+    day: Day,
+    state: &str,
+    account: Option<&str>,
+    missing: MissingInput,
+    download: impl FnOnce(Day) -> Result<String, String>,
+) -> Result<String, String> {
+    // solutions read the default account's input, named accounts download to their own directory.
+    if let Some(account) = account {
+        let path = session::data_dir(Some(account)).join(Resource::Input.file_name(day));
+        return Err(format!(
+            "The input of day {day} is {state}. AOC_ACCOUNT selects account `{account}`, pass `--input {}` to solve its input.",
+            path.display()
+        ));
+    }
+
+    let hint = format!(
+            "Download it with `cargo download {day}` or `cargo solve {day} --download`, or paste it into \"{}\".",
+            Resource::Input.path(day)
+        );
+
+    let should_download = match missing {
+        MissingInput::Fail => false,
+        MissingInput::Ask => confirm(
+            &format!("The input of day {day} is {state}. Download it now?"),
+            true,
+        ),
+        MissingInput::Download => true,
+    };
+    if !should_download {
+        return Err(format!("The input of day {day} is {state}. {hint}"));
+    }
+
+    download(day).map_err(|e| format!("Could not download the input of day {day}: {e}\n{hint}"))
+}
+
+/// Downloads the input of a day through the configured backend, replacing the unusable local file.
+fn download_input(day: Day) -> Result<String, String> {
+    let backend = backend::from_env()?;
+    let mut cache = Cache::open(backend.as_ref(), true)?;
+    cache.input(day)?;
+
+    let path = cache.path(Resource::Input, day);
+    println!(
+        "🎄 Downloaded the input of day {day} to \"{}\".",
+        path.display()
+    );
+    fs::read_to_string(&path).map_err(|e| format!("Could not read \"{}\": {e}", path.display()))
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::Puzzle => write!(f, "puzzle input"),
+            InputSource::Example(None) => write!(f, "example"),
+            InputSource::Example(Some(part)) => write!(f, "example for part {part}"),
+            InputSource::File(path) => write!(f, "input file {}", path.display()),
+            InputSource::Stdin => write!(f, "stdin"),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{ffi::OsString, fs, path::PathBuf};
+
+    use pico_args::Arguments;
+
+    use super::{handle_missing, InputSource, MissingInput};
+    use crate::day;
+    use crate::template::fixtures::tests::Sandbox;
+
+    fn parse(args: &[&str]) -> Result<InputSource, pico_args::Error> {
+        InputSource::from_args(&mut Arguments::from_vec(
+            args.iter().map(OsString::from).collect(),
+        ))
+    }
+
+    #[test]
+    fn parses_sources() {
+        assert_eq!(parse(&[]).unwrap(), InputSource::Puzzle);
+        assert_eq!(parse(&["--example"]).unwrap(), InputSource::Example(None));
+        assert_eq!(
+            parse(&["--example-part", "2"]).unwrap(),
+            InputSource::Example(Some(2))
+        );
+        assert_eq!(
+            parse(&["--input", "other.txt"]).unwrap(),
+            InputSource::File(PathBuf::from("other.txt"))
+        );
+        assert_eq!(parse(&["--input", "-"]).unwrap(), InputSource::Stdin);
+    }
+
+    #[test]
+    fn rejects_conflicting_sources() {
+        assert!(parse(&["--example", "--input", "other.txt"]).is_err());
+        assert!(parse(&["--example-part", "x"]).is_err());
+    }
+
+    #[test]
+    fn roundtrips_args() {
+        for source in [
+            InputSource::Puzzle,
+            InputSource::Example(None),
+            InputSource::Example(Some(1)),
+            InputSource::File(PathBuf::from("data/other.txt")),
+            InputSource::Stdin,
+        ] {
+            let args = source.to_args();
+            let args: Vec<&str> = args.iter().map(String::as_str).collect();
+            assert_eq!(parse(&args).unwrap(), source);
+        }
+    }
+
+    #[test]
+    fn resolves_paths() {
+        let path = InputSource::Example(Some(2)).path(day!(6)).unwrap();
+        assert!(path.ends_with("data/examples/06-2.txt"));
+
+        let path = InputSource::Puzzle.path(day!(6)).unwrap();
+        assert!(path.ends_with("data/inputs/06.txt"));
+
+        assert_eq!(InputSource::Stdin.path(day!(6)), None);
+        assert_eq!(
+            InputSource::Example(Some(2)).to_string(),
+            "example for part 2"
+        );
+    }
+
+    #[test]
+    fn explains_missing_puzzle_inputs() {
+        let not_downloaded = |_| -> Result<String, String> { panic!("should not download") };
+
+        let e = handle_missing(
+            day!(25),
+            "missing",
+            None,
+            MissingInput::Fail,
+            not_downloaded,
+        )
+        .unwrap_err();
+        assert!(
+            e.starts_with("The input of day 25 is missing. Download it with `cargo download 25`")
+        );
+
+        let e = handle_missing(
+            day!(25),
+            "empty",
+            Some("team"),
+            MissingInput::Download,
+            not_downloaded,
+        )
+        .unwrap_err();
+        assert!(e.starts_with("The input of day 25 is empty. AOC_ACCOUNT selects account `team`"));
+    }
+
+    #[test]
+    fn downloads_missing_puzzle_inputs() {
+        let input = handle_missing(day!(25), "empty", None, MissingInput::Download, |_| {
+            Ok("1   2\n".into())
+        });
+        assert_eq!(input, Ok("1   2\n".into()));
+
+        let e = handle_missing(day!(25), "missing", None, MissingInput::Download, |_| {
+            Err("offline".into())
+        })
+        .unwrap_err();
+        assert!(e.starts_with("Could not download the input of day 25: offline\nDownload it with"));
+    }
+
+    #[test]
+    fn reads_other_sources_as_they_are() {
+        let sandbox = Sandbox::new("input");
+
+        let missing = InputSource::File(sandbox.root.join("missing.txt"));
+        let e = missing
+            .read_or_download(day!(25), MissingInput::Download)
+            .unwrap_err();
+        assert!(e.starts_with("Could not read"));
+
+        let path = sandbox.root.join("empty.txt");
+        fs::write(&path, "").unwrap();
+        assert_eq!(
+            InputSource::File(path).read_or_download(day!(25), MissingInput::Download),
+            Ok(String::new())
+        );
+    }
+}
@@ -0,0 +1,211 @@
+/// Reads the JSON of a private leaderboard, as served by `/<year>/leaderboard/private/view/<id>.json`.
+use std::{
+    collections::{BTreeMap, HashMap},
+    str::FromStr,
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::Day;
+
+/// A member of a private leaderboard.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Member {
+    pub id: u64,
+    /// `None` for anonymous users.
+    pub name: Option<String>,
+    pub local_score: u64,
+    pub stars: u32,
+    /// When the stars were earned, in seconds since the unix epoch, by day.
+    pub days: BTreeMap<u8, [Option<u64>; 2]>,
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    /// The number of stars earned on a day.
+    pub fn stars_on(&self, day: u8) -> usize {
+        self.days
+            .get(&day)
+            .map_or(0, |parts| parts.iter().flatten().count())
+    }
+
+    /// The time between the first and the second star of a day, in seconds.
+    pub fn delta(&self, day: u8) -> Option<u64> {
+        match self.days.get(&day)? {
+            [Some(first), Some(second)] => Some(second.saturating_sub(*first)),
+            _ => None,
+        }
+    }
+}
+
+/// The members of a private leaderboard, ordered by local score.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Leaderboard {
+    pub members: Vec<Member>,
+}
+
+impl Leaderboard {
+    /// The last day that any member earned a star on.
+    pub fn last_day(&self) -> Option<u8> {
+        self.members
+            .iter()
+            .filter_map(|member| member.days.keys().next_back())
+            .max()
+            .copied()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl TryFrom<&str> for Leaderboard {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(value).or(Err("not valid JSON."))?;
+
+        let members = json
+            .get::<HashMap<String, JsonValue>>()
+            .and_then(|json| json.get("members"))
+            .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+            .ok_or("expected `json.members` to be an object.")?;
+
+        let mut members: Vec<Member> = members
+            .values()
+            .map(Member::try_from)
+            .collect::<Result<_, _>>()?;
+
+        members.sort_by(|a, b| {
+            b.local_score
+                .cmp(&a.local_score)
+                .then(b.stars.cmp(&a.stars))
+                .then(a.id.cmp(&b.id))
+        });
+
+        Ok(Leaderboard { members })
+    }
+}
+
+impl TryFrom<&JsonValue> for Member {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected member to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(integer)
+                .ok_or(format!("Expected member.{key} to be a number."))
+        };
+
+        let name = match json.get("name") {
+            Some(JsonValue::String(name)) => Some(name.clone()),
+            _ => None,
+        };
+
+        let levels = json
+            .get("completion_day_level")
+            .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+            .ok_or("Expected member.completion_day_level to be an object.")?;
+
+        let mut days = BTreeMap::new();
+        for (day, parts) in levels {
+            let day = day
+                .parse::<Day>()
+                .map_err(|_| format!("Expected day `{day}` to be a day of the event."))?
+                .into_inner();
+
+            let star = |part: &str| {
+                parts
+                    .get::<HashMap<String, JsonValue>>()
+                    .and_then(|parts| parts.get(part))
+                    .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+                    .and_then(|star| star.get("get_star_ts"))
+                    .and_then(integer)
+            };
+
+            days.insert(day, [star("1"), star("2")]);
+        }
+
+        Ok(Member {
+            id: number("id")?,
+            name,
+            local_score: number("local_score")?,
+            stars: number("stars")? as u32,
+            days,
+        })
+    }
+}
+
+/// Reads a non-negative integer that older leaderboards sometimes encode as a string.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn integer(value: &JsonValue) -> Option<u64> {
+    match value {
+        JsonValue::Number(n) if *n >= 0.0 => Some(*n as u64),
+        JsonValue::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Leaderboard;
+
+    const LEADERBOARD: &str = r#"{
+        "owner_id": 1, "event": "2024",
+        "members": {
+            "1": { "id": 1, "name": "Ada", "stars": 3, "local_score": 10, "global_score": 0, "last_star_ts": 1733036000,
+                "completion_day_level": {
+                    "1": { "1": { "get_star_ts": 1733029500, "star_index": 1 }, "2": { "get_star_ts": 1733029800, "star_index": 2 } },
+                    "2": { "1": { "get_star_ts": "1733116000", "star_index": 3 } }
+                } },
+            "2": { "id": 2, "name": null, "stars": 3, "local_score": 12, "global_score": 0, "last_star_ts": 0,
+                "completion_day_level": {
+                    "3": { "1": { "get_star_ts": 1733202000, "star_index": 4 }, "2": { "get_star_ts": 1733205725, "star_index": 5 } }
+                } }
+        }
+    }"#;
+
+    #[test]
+    fn parses_leaderboards() {
+        let leaderboard = Leaderboard::try_from(LEADERBOARD).unwrap();
+        let [first, second] = &leaderboard.members[..] else {
+            panic!("expected two members");
+        };
+
+        assert_eq!(first.display_name(), "(anonymous user #2)");
+        assert_eq!(first.delta(3), Some(3725));
+        assert_eq!(second.display_name(), "Ada");
+        assert_eq!(second.stars_on(1), 2);
+        assert_eq!(second.stars_on(2), 1);
+        assert_eq!(second.stars_on(3), 0);
+        assert_eq!(second.delta(1), Some(300));
+        assert_eq!(second.delta(2), None);
+        assert_eq!(leaderboard.last_day(), Some(3));
+    }
+
+    #[test]
+    fn rejects_other_pages() {
+        assert!(Leaderboard::try_from("<html></html>").is_err());
+        assert!(Leaderboard::try_from(r#"{ "members": [] }"#).is_err());
+    }
+
+    #[test]
+    fn rejects_days_outside_the_event() {
+        let leaderboard = LEADERBOARD.replace(r#""3": { "1""#, r#""26": { "1""#);
+        assert_eq!(
+            Leaderboard::try_from(leaderboard.as_str()).unwrap_err(),
+            "Expected day `26` to be a day of the event."
+        );
+    }
+}
@@ -0,0 +1,264 @@
+/// Converts the HTML of puzzle pages to markdown.
+/// Only covers the handful of tags the Advent of Code website uses in its articles.
+use std::fmt::Write;
+
+/// Returns the inner HTML of all `<article>` elements, in order.
+pub fn articles(html: &str) -> Vec<&str> {
+    let mut articles = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let Some(open) = rest[start..].find('>') else {
+            break;
+        };
+        let content = &rest[start + open + 1..];
+        let Some(end) = content.find("</article>") else {
+            break;
+        };
+        articles.push(&content[..end]);
+        rest = &content[end..];
+    }
+
+    articles
+}
+
+/// Converts an HTML fragment to markdown.
+pub fn to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    let mut rest = html;
+    let mut pre = false;
+    let mut links: Vec<String> = vec![];
+    let mut lists = 0usize;
+
+    while !rest.is_empty() {
+        let Some(start) = rest.find('<') else {
+            push_text(&mut out, rest, pre);
+            break;
+        };
+        push_text(&mut out, &rest[..start], pre);
+
+        let Some(end) = rest[start..].find('>') else {
+            push_text(&mut out, &rest[start..], pre);
+            break;
+        };
+        let tag = &rest[start + 1..start + end];
+        rest = &rest[start + end + 1..];
+
+        let (closing, tag) = match tag.strip_prefix('/') {
+            Some(tag) => (true, tag),
+            None => (false, tag),
+        };
+        let name_end = tag
+            .find(|c: char| c.is_whitespace() || c == '/')
+            .unwrap_or(tag.len());
+        let name = tag[..name_end].to_ascii_lowercase();
+        let attributes = &tag[name_end..];
+
+        match (name.as_str(), closing) {
+            ("h2", false) => {
+                end_block(&mut out);
+                out.push_str("## ");
+            }
+            ("h2" | "p", _) => end_block(&mut out),
+            ("em", _) => out.push('*'),
+            ("code", _) if !pre => out.push('`'),
+            ("pre", false) => {
+                end_block(&mut out);
+                out.push_str("```\n");
+                pre = true;
+            }
+            ("pre", true) => {
+                end_line(&mut out);
+                out.push_str("```");
+                end_block(&mut out);
+                pre = false;
+            }
+            ("ul", false) => {
+                if lists == 0 {
+                    end_block(&mut out);
+                }
+                lists += 1;
+            }
+            ("ul", true) => {
+                lists = lists.saturating_sub(1);
+                if lists == 0 {
+                    end_block(&mut out);
+                }
+            }
+            ("li", false) => {
+                end_line(&mut out);
+                out.push_str(&"  ".repeat(lists.saturating_sub(1)));
+                out.push_str("- ");
+            }
+            ("a", false) => {
+                out.push('[');
+                links.push(attribute(attributes, "href").unwrap_or_default());
+            }
+            ("a", true) => {
+                if let Some(href) = links.pop() {
+                    let _ = write!(out, "]({href})");
+                }
+            }
+            ("br", _) => out.push('\n'),
+            _ => {}
+        }
+    }
+
+    let mut markdown = out.trim().to_string();
+    markdown.push('\n');
+    markdown
+}
+
+/// Appends text, collapsing whitespace outside of `<pre>` blocks like a browser would.
+fn push_text(out: &mut String, text: &str, pre: bool) {
+    let text = decode_entities(text);
+
+    if pre {
+        out.push_str(&text);
+        return;
+    }
+
+    for (i, word) in text.split(char::is_whitespace).enumerate() {
+        if i > 0 && !out.is_empty() && !out.ends_with([' ', '\n']) {
+            out.push(' ');
+        }
+        out.push_str(word);
+    }
+}
+
+fn end_line(out: &mut String) {
+    trim_spaces(out);
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+}
+
+fn end_block(out: &mut String) {
+    end_line(out);
+    if !out.is_empty() && !out.ends_with("\n\n") {
+        out.push('\n');
+    }
+}
+
+fn trim_spaces(out: &mut String) {
+    out.truncate(out.trim_end_matches(' ').len());
+}
+
+fn attribute(attributes: &str, name: &str) -> Option<String> {
+    let start = attributes.find(&format!("{name}=\""))? + name.len() + 2;
+    let end = attributes[start..].find('"')?;
+    Some(decode_entities(&attributes[start..start + end]))
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let entity = rest
+            .find(';')
+            .filter(|&end| end <= 10)
+            .and_then(|end| Some((decode_entity(&rest[1..end])?, end)));
+
+        match entity {
+            Some((c, end)) => {
+                decoded.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    decoded.push_str(rest);
+    decoded
+}
+
+fn decode_entity(entity: &str) -> Option<char> {
+    match entity {
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "amp" => Some('&'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some('\u{a0}'),
+        _ => {
+            let code = match entity.strip_prefix("#x").or(entity.strip_prefix("#X")) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => entity.strip_prefix('#')?.parse().ok()?,
+            };
+            char::from_u32(code)
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{articles, to_markdown};
+
+    #[test]
+    fn finds_articles() {
+        let html = r#"<main><article class="day-desc"><h2>One</h2></article>
+            <p>Your puzzle answer was <code>1</code>.</p>
+            <article class="day-desc"><h2 id="part2">Two</h2></article></main>"#;
+
+        assert_eq!(
+            articles(html),
+            vec!["<h2>One</h2>", r#"<h2 id="part2">Two</h2>"#]
+        );
+        assert!(articles("<main></main>").is_empty());
+    }
+
+    #[test]
+    fn converts_puzzles() {
+        let html = r#"<h2>--- Day 1: Historian Hysteria ---</h2>
+<p>The <em>Chief Historian</em> is always
+present for the big <a href="/2024/day/25">launch</a>. Pair up the
+<em>smallest number</em> &amp; the <code>&lt;left&gt;</code> list:</p>
+<pre><code>3   4
+4   3
+</code></pre>
+<ul>
+<li>Pair <code>1</code> with <code>3</code>.</li>
+<li>Then:<ul><li>done.</li></ul></li>
+</ul>
+<p>What is the total distance&#x3F;</p>"#;
+
+        assert_eq!(
+            to_markdown(html),
+            [
+                "## --- Day 1: Historian Hysteria ---",
+                "",
+                "The *Chief Historian* is always present for the big [launch](/2024/day/25). Pair up the *smallest number* & the `<left>` list:",
+                "",
+                "```",
+                "3   4",
+                "4   3",
+                "```",
+                "",
+                "- Pair `1` with `3`.",
+                "- Then:",
+                "  - done.",
+                "",
+                "What is the total distance?",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn keeps_unknown_entities() {
+        assert_eq!(
+            to_markdown("<p>a &unknown; b & c</p>"),
+            "a &unknown; b & c\n"
+        );
+    }
+}
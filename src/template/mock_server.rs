@@ -0,0 +1,523 @@
+/// A stand-in for the Advent of Code website that serves a fixtures directory over HTTP, for exercising the commands offline.
+/// It answers the requests of the native backend: the home page, puzzle pages, inputs, answer submissions and private leaderboards.
+/// Point the commands at it with `AOC_BASE_URL`. The year in the requested paths is ignored.
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    io::{self, BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream},
+    time::{Duration, Instant},
+};
+
+use crate::template::aoc_client::{AocClientError, Fetched, SubmitResponse, Validators};
+use crate::template::backend::AocBackend;
+use crate::template::fixtures::Fixtures;
+use crate::template::submissions::Verdict;
+use crate::template::Day;
+
+/// The website asks to wait a minute after a wrong answer.
+pub const DEFAULT_COOLDOWN: Duration = Duration::from_secs(60);
+
+/// How long to wait for a request, so that a client that connects without sending one does not block the server.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Larger request bodies are refused with `413 Payload Too Large`, answers are short form bodies.
+const MAX_BODY_LENGTH: usize = 64 * 1024;
+
+/// The name that the home page shows for the logged in user.
+pub const USER_NAME: &str = "Mock User";
+
+struct Request {
+    method: String,
+    path: String,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+impl Request {
+    fn read(reader: &mut impl BufRead) -> io::Result<Request> {
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+
+        let mut parts = line.split_whitespace();
+        let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid request line {:?}", line.trim()),
+            ));
+        };
+        let method = method.to_string();
+        let path = target.split('?').next().unwrap_or_default().to_string();
+
+        let mut headers = vec![];
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                headers.push((name.trim().to_ascii_lowercase(), value.trim().to_string()));
+            }
+        }
+
+        let mut request = Request {
+            method,
+            path,
+            headers,
+            body: String::new(),
+        };
+
+        let length = request
+            .header("content-length")
+            .and_then(|length| length.parse().ok())
+            .unwrap_or(0);
+        if length > MAX_BODY_LENGTH {
+            return Err(io::Error::new(
+                io::ErrorKind::FileTooLarge,
+                format!("request body of {length} bytes is larger than {MAX_BODY_LENGTH} bytes"),
+            ));
+        }
+
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body)?;
+        request.body = String::from_utf8_lossy(&body).to_string();
+
+        Ok(request)
+    }
+
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn session(&self) -> Option<&str> {
+        self.header("cookie")?
+            .split(';')
+            .find_map(|cookie| cookie.trim().strip_prefix("session="))
+            .filter(|session| !session.is_empty())
+    }
+
+    /// Reads a field of an url-encoded form body.
+    fn form(&self, name: &str) -> Option<String> {
+        self.body.split('&').find_map(|field| {
+            let (key, value) = field.split_once('=')?;
+            (key == name).then(|| url_decode(value))
+        })
+    }
+}
+
+fn url_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut out = vec![];
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => out.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+                match hex.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                    Some(byte) => {
+                        out.push(byte);
+                        i += 2;
+                    }
+                    None => out.push(b'%'),
+                }
+            }
+            byte => out.push(byte),
+        }
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&out).to_string()
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Response {
+    status: u16,
+    content_type: &'static str,
+    etag: Option<String>,
+    body: String,
+}
+
+impl Response {
+    fn html(status: u16, body: String) -> Self {
+        Response {
+            status,
+            content_type: "text/html",
+            etag: None,
+            body,
+        }
+    }
+
+    fn text(status: u16, body: String) -> Self {
+        Response {
+            content_type: "text/plain",
+            ..Response::html(status, body)
+        }
+    }
+
+    /// Tags the response with a hash of its body, and responds with `304 Not Modified` if the client has it already.
+    fn tagged(self, request: &Request) -> Self {
+        let mut hasher = DefaultHasher::new();
+        self.body.hash(&mut hasher);
+        let etag = format!("\"{:x}\"", hasher.finish());
+
+        if request.header("if-none-match") == Some(etag.as_str()) {
+            Response {
+                status: 304,
+                etag: Some(etag),
+                body: String::new(),
+                ..self
+            }
+        } else {
+            Response {
+                etag: Some(etag),
+                ..self
+            }
+        }
+    }
+
+    fn write_to(&self, stream: &mut impl Write) -> io::Result<()> {
+        let reason = match self.status {
+            200 => "OK",
+            304 => "Not Modified",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            413 => "Payload Too Large",
+            _ => "Internal Server Error",
+        };
+
+        write!(stream, "HTTP/1.1 {} {reason}\r\n", self.status)?;
+        write!(stream, "Content-Type: {}\r\n", self.content_type)?;
+        if let Some(etag) = &self.etag {
+            write!(stream, "ETag: {etag}\r\n")?;
+        }
+        write!(
+            stream,
+            "Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+            self.body.len(),
+            self.body
+        )?;
+        stream.flush()
+    }
+}
+
+/// Serves a fixtures directory like the website does. Requests are answered one at a time.
+pub struct MockServer {
+    fixtures: Fixtures,
+    /// The only session that is logged in, any session is if `None`.
+    session: Option<String>,
+    /// How long answers are refused after a wrong answer.
+    cooldown: Duration,
+    wait_until: Option<Instant>,
+    read_timeout: Duration,
+}
+
+impl MockServer {
+    pub fn new(fixtures: Fixtures, session: Option<String>, cooldown: Duration) -> Self {
+        MockServer {
+            fixtures,
+            session,
+            cooldown,
+            wait_until: None,
+            read_timeout: READ_TIMEOUT,
+        }
+    }
+
+    /// Answers the connections of a listener until accepting one fails.
+    pub fn serve(&mut self, listener: &TcpListener) -> io::Result<()> {
+        for stream in listener.incoming() {
+            if let Err(e) = self.handle(stream?) {
+                eprintln!("Failed to answer a request: {e}");
+            }
+        }
+        Ok(())
+    }
+
+    fn handle(&mut self, mut stream: TcpStream) -> io::Result<()> {
+        stream.set_read_timeout(Some(self.read_timeout))?;
+        let request = match Request::read(&mut BufReader::new(&stream)) {
+            Err(e) if e.kind() == io::ErrorKind::FileTooLarge => {
+                eprintln!("Refused a request: {e}");
+                return Response::text(413, format!("The {e}.\n")).write_to(&mut stream);
+            }
+            request => request?,
+        };
+        let response = self.respond(&request);
+
+        println!("{} {} {}", request.method, request.path, response.status);
+        response.write_to(&mut stream)
+    }
+
+    fn respond(&mut self, request: &Request) -> Response {
+        let segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+        let logged_in = match (&self.session, request.session()) {
+            (_, None) => false,
+            (Some(expected), Some(session)) => expected == session,
+            (None, Some(_)) => true,
+        };
+
+        let (year, rest) = match segments.split_first() {
+            Some((year, rest)) if year.parse::<u16>().is_ok() => (*year, rest),
+            _ => return not_found(),
+        };
+
+        match (request.method.as_str(), rest) {
+            ("GET", []) if logged_in => Response::html(
+                200,
+                format!("<header><div class=\"user\">{USER_NAME} <span class=\"star-count\">0*</span></div></header>\n<main><p>Advent of Code {year}</p></main>"),
+            ),
+            (_, ["day", _, "input"]) if !logged_in => Response::text(
+                400,
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n".into(),
+            ),
+            _ if !logged_in => Response::html(
+                200,
+                "<main><p>To play, please identify yourself: <a href=\"/auth/login\">[Log In]</a></p></main>".into(),
+            ),
+            ("GET", ["day", day]) => match parse_day(day) {
+                Some(day) => self.fixture(day, self.fixtures.puzzle_page(day), request),
+                None => not_found(),
+            },
+            ("GET", ["day", day, "input"]) => match parse_day(day) {
+                Some(day) => {
+                    let input = self
+                        .fixtures
+                        .input(day, &Validators::default())
+                        .map(|fetched| match fetched {
+                            Fetched::Modified(input, _) => input,
+                            Fetched::NotModified => String::new(),
+                        });
+                    Response {
+                        content_type: "text/plain",
+                        ..self.fixture(day, input, request)
+                    }
+                }
+                None => not_found(),
+            },
+            ("POST", ["day", day, "answer"]) => match parse_day(day) {
+                Some(day) => self.submit(day, request),
+                None => not_found(),
+            },
+            ("GET", ["leaderboard", "private", "view", file]) => {
+                match file.strip_suffix(".json").and_then(|id| id.parse().ok()) {
+                    Some(id) => match self.fixtures.leaderboard(id) {
+                        Ok(json) => Response {
+                            content_type: "application/json",
+                            ..Response::html(200, json)
+                        },
+                        Err(e) => error(e),
+                    },
+                    None => not_found(),
+                }
+            }
+            ("GET", _) => not_found(),
+            _ => Response::text(405, "Method not allowed.\n".into()),
+        }
+    }
+
+    /// Responds with a fixture of a day, or with the response of the website to a day that is not unlocked.
+    fn fixture(
+        &self,
+        day: Day,
+        content: Result<String, AocClientError>,
+        request: &Request,
+    ) -> Response {
+        match content {
+            Ok(content) => Response::html(200, content).tagged(request),
+            Err(AocClientError::Status { status: 404, .. }) => Response::text(
+                404,
+                format!("Please don't repeatedly request this endpoint before it unlocks! The calendar countdown is synchronized with the server time; the link will be enabled on the calendar the instant this puzzle becomes available. It unlocks on December {} at midnight EST.\n", day.into_inner()),
+            ),
+            Err(e) => error(e),
+        }
+    }
+
+    fn submit(&mut self, day: Day, request: &Request) -> Response {
+        let (Some(part), Some(answer)) = (
+            request.form("level").and_then(|level| level.parse().ok()),
+            request.form("answer"),
+        ) else {
+            return Response::text(400, "Expected a level and an answer.\n".into());
+        };
+
+        let now = Instant::now();
+        if let Some(left) = self
+            .wait_until
+            .and_then(|until| until.checked_duration_since(now))
+        {
+            return Response::html(
+                200,
+                format!("<main>\n<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have {}s left to wait. <a href=\"/{}\">[Return to Day {}]</a></p></article>\n</main>", left.as_secs() + 1, day.into_inner(), day.into_inner()),
+            );
+        }
+
+        match self.fixtures.judge_submission(day, part, &answer) {
+            Ok(html) => {
+                let verdict = Verdict::from_response(&SubmitResponse::from_html(&html).message);
+                if verdict.is_some_and(|verdict| verdict != Verdict::Correct) {
+                    self.wait_until = Some(now + self.cooldown);
+                }
+                Response::html(200, html)
+            }
+            Err(e) => error(e),
+        }
+    }
+}
+
+fn parse_day(day: &str) -> Option<Day> {
+    Day::new(day.parse().ok()?)
+}
+
+fn not_found() -> Response {
+    Response::text(404, "404 Not Found\n".into())
+}
+
+fn error(e: AocClientError) -> Response {
+    match e {
+        AocClientError::Status { status: 404, .. } => not_found(),
+        e => Response::text(500, format!("{e}\n")),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        io::{Read, Write},
+        net::{TcpListener, TcpStream},
+        thread,
+        time::Duration,
+    };
+
+    use super::{url_decode, MockServer, USER_NAME};
+    use crate::day;
+    use crate::template::aoc_client::{AocClient, AocClientError, Fetched, SubmitOutcome};
+    use crate::template::fixtures::{tests::Sandbox, Fixtures};
+    use crate::template::submissions::Verdict;
+
+    /// Starts a server for the fixtures in a background thread, and returns its base url.
+    fn start(fixtures: Fixtures, session: Option<&str>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let mut server = MockServer {
+            read_timeout: Duration::from_millis(200),
+            ..MockServer::new(fixtures, session.map(Into::into), Duration::from_secs(60))
+        };
+        thread::spawn(move || server.serve(&listener));
+
+        base_url
+    }
+
+    #[test]
+    fn serves_the_website() {
+        let sandbox = Sandbox::new("mock-server");
+        let base_url = start(sandbox.fixtures.clone(), None);
+        let client = AocClient::new(&base_url, 2024, "abc");
+
+        assert_eq!(client.validate().unwrap(), USER_NAME);
+
+        let Fetched::Modified(input, validators) =
+            client.input(day!(1), &Default::default()).unwrap()
+        else {
+            panic!("expected an input");
+        };
+        assert_eq!(input, "1   2\n");
+        assert!(validators.etag.is_some());
+        assert_eq!(
+            client.input(day!(1), &validators).unwrap(),
+            Fetched::NotModified
+        );
+        assert!(matches!(
+            client.input(day!(2), &Default::default()),
+            Err(AocClientError::NotUnlocked)
+        ));
+
+        let Fetched::Modified(puzzle, _) = client.puzzle(day!(1), &Default::default()).unwrap()
+        else {
+            panic!("expected a puzzle");
+        };
+        assert_eq!(puzzle.parts, 1);
+
+        assert!(client.leaderboard(7).unwrap().contains("Ada"));
+        assert!(matches!(
+            client.leaderboard(8),
+            Err(AocClientError::Status { status: 404, .. })
+        ));
+    }
+
+    #[test]
+    fn judges_and_rate_limits_answers() {
+        let sandbox = Sandbox::new("mock-server-submit");
+        let base_url = start(sandbox.fixtures.clone(), None);
+        let client = AocClient::new(&base_url, 2024, "abc");
+
+        assert_eq!(
+            client.submit(day!(1), 1, "12").unwrap().outcome,
+            SubmitOutcome::Judged(Verdict::TooHigh)
+        );
+        assert_eq!(
+            client.submit(day!(1), 1, "11").unwrap().outcome,
+            SubmitOutcome::TooRecent(Some("60s".into()))
+        );
+    }
+
+    #[test]
+    fn rejects_other_sessions() {
+        let sandbox = Sandbox::new("mock-server-session");
+        let base_url = start(sandbox.fixtures.clone(), Some("abc"));
+
+        let client = AocClient::new(&base_url, 2024, "def");
+        assert!(matches!(
+            client.validate(),
+            Err(AocClientError::LoggedOut(None))
+        ));
+        assert!(matches!(
+            client.input(day!(1), &Default::default()),
+            Err(AocClientError::LoggedOut(None))
+        ));
+
+        let client = AocClient::new(&base_url, 2024, "abc");
+        assert!(client.input(day!(1), &Default::default()).is_ok());
+    }
+
+    #[test]
+    fn drops_idle_connections() {
+        let sandbox = Sandbox::new("mock-server-idle");
+        let base_url = start(sandbox.fixtures.clone(), None);
+
+        let _idle = TcpStream::connect(base_url.trim_start_matches("http://")).unwrap();
+        let client = AocClient::new(&base_url, 2024, "abc");
+        assert_eq!(client.validate().unwrap(), USER_NAME);
+    }
+
+    #[test]
+    fn refuses_large_bodies() {
+        let sandbox = Sandbox::new("mock-server-large");
+        let base_url = start(sandbox.fixtures.clone(), None);
+
+        let mut stream = TcpStream::connect(base_url.trim_start_matches("http://")).unwrap();
+        write!(
+            stream,
+            "POST /2024/day/1/answer HTTP/1.1\r\nContent-Length: 4294967296\r\n\r\n"
+        )
+        .unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 413 Payload Too Large\r\n"));
+    }
+
+    #[test]
+    fn decodes_form_values() {
+        assert_eq!(url_decode("a+b%2Cc"), "a b,c");
+        assert_eq!(url_decode("100%"), "100%");
+        assert_eq!(url_decode("%zz"), "%zz");
+    }
+}
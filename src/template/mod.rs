@@ -1,27 +1,82 @@
-use std::{env, fs};
+use std::{
+    env, fs,
+    io::{stdin, stdout, IsTerminal, Write},
+};
 
 pub mod aoc_cli;
+pub mod aoc_client;
+pub mod backend;
+pub mod bench;
 pub mod commands;
+pub mod examples;
+pub mod fixtures;
+pub mod input;
+pub mod memory;
+pub mod mock_server;
 pub mod runner;
 
 pub use day::*;
 
+mod answers;
+mod cache;
+mod compare;
+mod cpu;
 mod day;
+mod leaderboard;
+mod markdown;
+mod puzzle;
 mod readme_benchmarks;
+mod readme_stars;
+mod report;
 mod run_multi;
+mod session;
+mod stats;
+mod submissions;
 mod timings;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
+pub const ANSI_RED: &str = "\x1b[31m";
+pub const ANSI_GREEN: &str = "\x1b[32m";
+pub const ANSI_YELLOW: &str = "\x1b[33m";
+
+/// Returns the year of the event in `AOC_YEAR`.
+pub(crate) fn year_from_env() -> Result<u16, String> {
+    env::var("AOC_YEAR")
+        .ok()
+        .and_then(|year| year.parse().ok())
+        .ok_or_else(|| "AOC_YEAR is not set or not a year, set it in .cargo/config.toml.".into())
+}
+
+/// Asks a yes or no question on the terminal, an empty answer picks `default`.
+/// Answers no without asking if there is no terminal, e.g. in CI.
+pub(crate) fn confirm(question: &str, default: bool) -> bool {
+    if !stdin().is_terminal() || !stdout().is_terminal() {
+        return false;
+    }
+
+    print!("{question} {} ", if default { "[Y/n]" } else { "[y/N]" });
+    let _ = stdout().flush();
+
+    let mut answer = String::new();
+    if stdin().read_line(&mut answer).is_err() {
+        return false;
+    }
+
+    match answer.trim().to_lowercase().as_str() {
+        "" => default,
+        answer => matches!(answer, "y" | "yes"),
+    }
+}
 
 /// Helper function that reads a text file to a string.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    let f = fs::read_to_string(&filepath);
+    f.unwrap_or_else(|e| panic!("could not open input file \"{}\": {e}", filepath.display()))
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
@@ -32,13 +87,16 @@ pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
         .join("data")
         .join(folder)
         .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    let f = fs::read_to_string(&filepath);
+    f.unwrap_or_else(|e| panic!("could not open input file \"{}\": {e}", filepath.display()))
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// Passing `parse` declares a shared parse stage: the function `parse(input: &str)` is run (and timed) once
+/// and a reference to its output is passed to both parts, e.g. `solution!(1, parse)` or `solution!(1, parse, 2)`.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
@@ -50,8 +108,38 @@ macro_rules! solution {
     ($day:expr, 2) => {
         $crate::solution!(@impl $day, [part_two, 2]);
     };
+    ($day:expr, parse) => {
+        $crate::solution!(@impl_parse $day, parse, [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, parse, 1) => {
+        $crate::solution!(@impl_parse $day, parse, [part_one, 1]);
+    };
+    ($day:expr, parse, 2) => {
+        $crate::solution!(@impl_parse $day, parse, [part_two, 2]);
+    };
 
     (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@setup $day);
+
+        fn main() {
+            use $crate::template::runner::*;
+            let input = read_input(DAY);
+            $( run_part($func, input, DAY, $part); )*
+        }
+    };
+
+    (@impl_parse $day:expr, $parse:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@setup $day);
+
+        fn main() {
+            use $crate::template::runner::*;
+            let input = read_input(DAY);
+            let parsed = Box::leak(Box::new(run_parse($parse, input)));
+            $( run_part($func, &*parsed, DAY, $part); )*
+        }
+    };
+
+    (@setup $day:expr) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
@@ -59,10 +147,58 @@ macro_rules! solution {
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
-        fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
-        }
+        // only one global allocator can be installed, dhat takes precedence if both features are enabled.
+        #[cfg(all(feature = "count-allocations", not(feature = "dhat-heap")))]
+        #[global_allocator]
+        static ALLOC: $crate::template::memory::CountingAlloc = $crate::template::memory::CountingAlloc;
+    };
+}
+
+/// Generates a test for each part that checks the answers of all examples listed in the day's manifest, `data/examples/<day>.json`.
+/// Call it inside the test module of a solution, with the same arguments that are passed to `solution!` after the day:
+/// e.g. `example_tests!()`, `example_tests!(2)` or `example_tests!(parse)`.
+///
+/// A failing test lists every example that produced a wrong answer or panicked.
+///
+/// Unlike a test per manifest entry, there is one test per part: the manifest is read when the tests run,
+/// and a declarative macro cannot generate tests from the contents of a file.
+/// Adding an example to the manifest therefore does not require recompiling the tests, and a failing example does not hide the others.
+#[macro_export]
+macro_rules! example_tests {
+    () => {
+        $crate::example_tests!(@impl [part_one, 1, part_one_examples] [part_two, 2, part_two_examples]);
+    };
+    (1) => {
+        $crate::example_tests!(@impl [part_one, 1, part_one_examples]);
+    };
+    (2) => {
+        $crate::example_tests!(@impl [part_two, 2, part_two_examples]);
+    };
+    (parse) => {
+        $crate::example_tests!(@impl_parse parse, [part_one, 1, part_one_examples] [part_two, 2, part_two_examples]);
+    };
+    (parse, 1) => {
+        $crate::example_tests!(@impl_parse parse, [part_one, 1, part_one_examples]);
+    };
+    (parse, 2) => {
+        $crate::example_tests!(@impl_parse parse, [part_two, 2, part_two_examples]);
+    };
+
+    (@impl $( [$func:ident, $part:expr, $test:ident] )*) => {
+        $(
+            #[test]
+            fn $test() {
+                $crate::template::examples::check(DAY, $part, |input| $func(input));
+            }
+        )*
+    };
+
+    (@impl_parse $parse:ident, $( [$func:ident, $part:expr, $test:ident] )*) => {
+        $(
+            #[test]
+            fn $test() {
+                $crate::template::examples::check(DAY, $part, |input| $func(&$parse(input)));
+            }
+        )*
     };
 }
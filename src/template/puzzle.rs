@@ -0,0 +1,287 @@
+/// Finds example inputs and their answers in the puzzle descriptions that `cargo download` writes to `data/puzzles`.
+/// Examples are the code blocks introduced by a paragraph that mentions an example, answers are the emphasised values in the text around them.
+use std::mem;
+
+/// A guess at a value in the puzzle description.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Guess {
+    /// There is one likely candidate.
+    Sure(String),
+    /// There are several candidates, the most likely first.
+    Unsure(Vec<String>),
+    None,
+}
+
+/// The example input and answer of a part.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PartExamples {
+    pub input: Guess,
+    pub answer: Guess,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Block {
+    Text(String),
+    Code(String),
+}
+
+/// Finds the example of a part. Returns `None` if the description does not contain the part yet.
+pub fn find(markdown: &str, part: u8) -> Option<PartExamples> {
+    let sections = sections(markdown);
+    let blocks = sections.get(usize::from(part).checked_sub(1)?)?;
+
+    Some(PartExamples {
+        input: find_input(blocks),
+        answer: find_answer(blocks),
+    })
+}
+
+/// Splits the description into one list of blocks per part, each starting at a `## ` heading.
+fn sections(markdown: &str) -> Vec<Vec<Block>> {
+    let mut sections: Vec<Vec<Block>> = vec![];
+    let mut text: Vec<&str> = vec![];
+    let mut code: Option<Vec<&str>> = None;
+
+    let flush = |sections: &mut Vec<Vec<Block>>, text: &mut Vec<&str>| {
+        let text = mem::take(text);
+        if let (Some(section), false) = (sections.last_mut(), text.is_empty()) {
+            section.push(Block::Text(text.join("\n")));
+        }
+    };
+
+    for line in markdown.lines() {
+        if let Some(lines) = &mut code {
+            if line.starts_with("```") {
+                if let Some(section) = sections.last_mut() {
+                    let mut block = lines.join("\n");
+                    block.push('\n');
+                    section.push(Block::Code(block));
+                }
+                code = None;
+            } else {
+                lines.push(line);
+            }
+        } else if line.starts_with("## ") {
+            flush(&mut sections, &mut text);
+            sections.push(vec![]);
+        } else if line.starts_with("```") {
+            flush(&mut sections, &mut text);
+            code = Some(vec![]);
+        } else if line.trim().is_empty() {
+            flush(&mut sections, &mut text);
+        } else {
+            text.push(line);
+        }
+    }
+
+    flush(&mut sections, &mut text);
+    sections
+}
+
+fn mentions_example(text: &str) -> bool {
+    text.to_lowercase().contains("example")
+}
+
+/// The example input is the code block right after a paragraph that mentions an example.
+fn find_input(blocks: &[Block]) -> Guess {
+    let mut introduced: Vec<String> = vec![];
+    let mut others: Vec<String> = vec![];
+    let mut intro: Option<&str> = None;
+
+    for block in blocks {
+        match block {
+            Block::Text(text) => intro = Some(text),
+            Block::Code(code) => {
+                if intro.is_some_and(mentions_example) {
+                    introduced.push(code.clone());
+                } else {
+                    others.push(code.clone());
+                }
+                intro = None;
+            }
+        }
+    }
+
+    match introduced.len() {
+        1 => Guess::Sure(introduced.remove(0)),
+        _ => guess(introduced.into_iter().chain(others)),
+    }
+}
+
+/// The answer is the emphasised value in the last paragraph that talks about the example.
+/// The closing paragraph asks for the answer to the real input, so it is skipped.
+fn find_answer(blocks: &[Block]) -> Guess {
+    let mut example_values: Vec<String> = vec![];
+    let mut values: Vec<String> = vec![];
+
+    for block in blocks {
+        let Block::Text(text) = block else {
+            continue;
+        };
+        if text.trim_end_matches(['*', ' ']).ends_with('?') {
+            continue;
+        }
+
+        let found = emphasised_values(text);
+        if mentions_example(text) && !found.is_empty() {
+            example_values.clone_from(&found);
+        }
+        values.extend(found);
+    }
+
+    match example_values.len() {
+        1 => Guess::Sure(example_values.remove(0)),
+        _ => guess(
+            example_values
+                .into_iter()
+                .rev()
+                .chain(values.into_iter().rev()),
+        ),
+    }
+}
+
+fn guess(candidates: impl Iterator<Item = String>) -> Guess {
+    let mut unique: Vec<String> = vec![];
+    for candidate in candidates {
+        if !unique.contains(&candidate) {
+            unique.push(candidate);
+        }
+    }
+
+    if unique.is_empty() {
+        Guess::None
+    } else {
+        Guess::Unsure(unique)
+    }
+}
+
+/// Returns the emphasised values of a text, e.g. `41` for `` `*41*` `` or `*41*`.
+/// Like in markdown, only a `*` before non-whitespace opens an emphasis, so `` `3 * 3` `` is not one.
+/// Emphasised words that cannot be an answer, such as `*not*`, are skipped.
+fn emphasised_values(text: &str) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut values = vec![];
+    let mut open: Option<usize> = None;
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c != '*' {
+            continue;
+        }
+
+        let before = i.checked_sub(1).map(|j| chars[j]);
+        let after = chars.get(i + 1);
+
+        match open {
+            Some(start) if before.is_some_and(|c| !c.is_whitespace()) => {
+                values.push(chars[start + 1..i].iter().collect::<String>());
+                open = None;
+            }
+            _ if after.is_some_and(|c| !c.is_whitespace()) => open = Some(i),
+            _ => {}
+        }
+    }
+
+    values
+        .iter()
+        .map(|value| value.trim_matches('`'))
+        .filter(|value| is_value(value))
+        .map(Into::into)
+        .collect()
+}
+
+fn is_value(value: &str) -> bool {
+    value.contains(char::is_alphanumeric)
+        && value.len() <= 40
+        && !value.contains(char::is_whitespace)
+        && (value.contains(|c: char| c.is_ascii_digit())
+            || value
+                .chars()
+                .all(|c| !c.is_alphabetic() || c.is_uppercase()))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{emphasised_values, find, Guess, PartExamples};
+
+    const PUZZLE: &str = "## --- Day 1: Historian Hysteria ---
+
+The *Chief Historian* is always present. There's just one problem: by holding the two lists up *side by side*, it quickly becomes clear that the lists aren't very similar.
+
+For example:
+
+```
+3   4
+4   3
+```
+
+Maybe the lists are only off by a small amount! Pair up the smallest number in the left list with the smallest number in the right list.
+
+```
+1   3
+2   5
+```
+
+In the example above, this would be 2 + 1, a total distance of `*3*`!
+
+Your actual left and right lists contain many location IDs. *What is the total distance between your lists?*
+
+## --- Part Two ---
+
+The first number in the left list is `3`. It appears in the right list three times, so the similarity score increases by `3 * 3 = *9*`.
+
+Here is a larger example:
+
+```
+1   1
+```
+
+So, for these example lists, the similarity score is `*31*` (`9 + 4 + 0`), and the *CMZ* crate is not `*0*`.
+
+Once again consider your left and right lists. *What is their similarity score?*
+";
+
+    #[test]
+    fn finds_examples() {
+        assert_eq!(
+            find(PUZZLE, 1),
+            Some(PartExamples {
+                input: Guess::Sure("3   4\n4   3\n".into()),
+                answer: Guess::Sure("3".into()),
+            })
+        );
+
+        assert_eq!(
+            find(PUZZLE, 2),
+            Some(PartExamples {
+                input: Guess::Sure("1   1\n".into()),
+                answer: Guess::Unsure(vec!["0".into(), "CMZ".into(), "31".into(), "9".into()]),
+            })
+        );
+
+        assert_eq!(find(PUZZLE, 3), None);
+        assert_eq!(find(PUZZLE, 0), None);
+    }
+
+    #[test]
+    fn is_unsure_without_introduction() {
+        let puzzle = "## --- Day 2 ---\n\nLook:\n\n```\na\n```\n\n```\nb\n```\n";
+
+        assert_eq!(
+            find(puzzle, 1),
+            Some(PartExamples {
+                input: Guess::Unsure(vec!["a\n".into(), "b\n".into()]),
+                answer: Guess::None,
+            })
+        );
+    }
+
+    #[test]
+    fn finds_emphasised_values() {
+        assert_eq!(
+            emphasised_values("got *41* and `*4,6,3*`, `3 * 3 = *9*`, *not* *the answer*, *CMZ*"),
+            vec!["41", "4,6,3", "9", "CMZ"]
+        );
+    }
+}
@@ -2,7 +2,10 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
-use crate::template::timings::Timings;
+use crate::template::cpu::CpuTime;
+use crate::template::memory::format_bytes;
+use crate::template::report::Status;
+use crate::template::timings::{StageTiming, Timings};
 use crate::template::Day;
 
 static MARKER: &str = "<!--- benchmarking table --->";
@@ -21,8 +24,8 @@ impl From<std::io::Error> for Error {
 }
 
 pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+    pub(crate) pos_start: usize,
+    pub(crate) pos_end: usize,
 }
 
 #[must_use]
@@ -30,13 +33,15 @@ pub fn get_path_for_bin(day: Day) -> String {
     format!("./src/bin/{day}.rs")
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+/// Finds the section between the first and the last occurrence of `marker`, including the markers.
+/// A single marker is an empty section, which the new table replaces.
+pub(crate) fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
-        return Err(Error::Parser(
-            "{}: too many occurences of marker in README.".into(),
-        ));
+        return Err(Error::Parser(format!(
+            "{marker}: too many occurences of marker in README."
+        )));
     }
 
     let pos_start = matches
@@ -59,18 +64,19 @@ fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String
         MARKER.into(),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        "| Day | Parse | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: | :---:  |".into(),
     ];
 
     for timing in timings.data {
         let path = get_path_for_bin(timing.day);
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}) | {} | {} | {} |",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            format_cell(&timing.parse),
+            format_cell(&timing.part_1),
+            format_cell(&timing.part_2)
         ));
     }
 
@@ -81,8 +87,39 @@ fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String
     lines.join("\n")
 }
 
+/// Formats a table cell, appending the CPU time and peak heap usage if they were measured.
+fn format_cell(stage: &StageTiming) -> String {
+    match stage.status {
+        Some(Status::TimedOut) => return "`✖ timed out`".into(),
+        Some(Status::Panicked) => return "`✖ panicked`".into(),
+        Some(Status::Error) => return "`✖ error`".into(),
+        _ => {}
+    }
+
+    let time = format!("`{}`", stage.time.as_deref().unwrap_or("-"));
+
+    let details: Vec<String> = stage
+        .cpu
+        .as_ref()
+        .map(CpuTime::summary)
+        .into_iter()
+        .chain(
+            stage
+                .memory
+                .as_ref()
+                .map(|memory| format_bytes(memory.peak_bytes)),
+        )
+        .collect();
+
+    if details.is_empty() {
+        time
+    } else {
+        format!("{time} ({})", details.join(", "))
+    }
+}
+
 fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
+    let positions = locate_table(s, MARKER)?;
     let table = construct_table("##", timings, total_millis);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
@@ -99,29 +136,40 @@ pub fn update(timings: Timings) -> Result<(), Error> {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{format_cell, update_content, MARKER};
+    use crate::{
+        day, template::cpu::CpuTime, template::memory::Memory, template::report::Status,
+        template::timings::StageTiming, template::timings::Timing, template::timings::Timings,
+    };
+
+    /// A stage that was timed at `time`.
+    fn timed(time: &str) -> StageTiming {
+        StageTiming {
+            time: Some(time.into()),
+            ..StageTiming::default()
+        }
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    part_1: timed("10ms"),
+                    part_2: timed("20ms"),
                     total_nanos: 3e+10,
+                    ..Timing::new(day!(1))
                 },
                 Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    part_1: timed("30ms"),
+                    part_2: timed("40ms"),
                     total_nanos: 7e+10,
+                    ..Timing::new(day!(2))
                 },
                 Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
+                    part_1: timed("40ms"),
+                    part_2: timed("50ms"),
                     total_nanos: 9e+10,
+                    ..Timing::new(day!(4))
                 },
             ],
         }
@@ -167,11 +215,11 @@ mod tests {
             "<!--- benchmarking table --->",
             "## Benchmarks",
             "",
-            "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| Day | Parse | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/01.rs) | `-` | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/02.rs) | `-` | `30ms` | `40ms` |",
+            "| [Day 4](./src/bin/04.rs) | `-` | `40ms` | `50ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +228,63 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn formats_parse_cells() {
+        assert_eq!(format_cell(&timed("5ms")), "`5ms`");
+        assert_eq!(format_cell(&StageTiming::default()), "`-`");
+    }
+
+    #[test]
+    fn formats_failed_stages() {
+        for (status, expected) in [
+            (Status::TimedOut, "`✖ timed out`"),
+            (Status::Panicked, "`✖ panicked`"),
+            (Status::Error, "`✖ error`"),
+        ] {
+            let stage = StageTiming {
+                status: Some(status),
+                ..StageTiming::default()
+            };
+            assert_eq!(format_cell(&stage), expected);
+        }
+
+        let stage = StageTiming {
+            status: Some(Status::Ok),
+            ..timed("30ms")
+        };
+        assert_eq!(format_cell(&stage), "`30ms`");
+    }
+
+    #[test]
+    fn formats_cpu_and_memory() {
+        let cpu = CpuTime {
+            nanos: 160_000_000.0,
+            ratio: 4.0,
+        };
+        let memory = Memory {
+            peak_bytes: 2048,
+            allocations: 1,
+            allocated_bytes: 2048,
+        };
+
+        let stage = StageTiming {
+            cpu: Some(cpu.clone()),
+            memory: Some(memory.clone()),
+            ..timed("40ms")
+        };
+        assert_eq!(format_cell(&stage), "`40ms` (cpu 160.0ms ×4.00, 2.0 KiB)");
+
+        let stage = StageTiming {
+            cpu: Some(cpu),
+            ..timed("40ms")
+        };
+        assert_eq!(format_cell(&stage), "`40ms` (cpu 160.0ms ×4.00)");
+
+        let stage = StageTiming {
+            memory: Some(memory),
+            ..timed("40ms")
+        };
+        assert_eq!(format_cell(&stage), "`40ms` (2.0 KiB)");
+    }
 }
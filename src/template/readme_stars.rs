@@ -0,0 +1,223 @@
+/// Module that updates the readme with the stars earned on each day, in the format of `advent-readme-stars`.
+/// Stars come from the local answers and submission history, or from the user's entry on a private leaderboard.
+use std::{collections::BTreeMap, fs};
+
+use crate::template::answers::Answers;
+use crate::template::leaderboard::Member;
+use crate::template::readme_benchmarks::{locate_table, Error};
+use crate::template::submissions::{History, Verdict};
+use crate::template::{all_days, Day};
+
+static MARKER: &str = "<!--- advent_readme_stars table --->";
+
+/// The solved parts of each day.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Stars {
+    pub days: BTreeMap<Day, [bool; 2]>,
+}
+
+impl Stars {
+    /// A part counts as solved if its answer is known or one of its submissions was correct.
+    pub fn from_local(answers: &Answers, history: &History) -> Self {
+        let mut stars = Stars::default();
+
+        for day_answers in &answers.data {
+            for part in [1, 2] {
+                if day_answers.get(part).is_some() {
+                    stars.add(day_answers.day, part);
+                }
+            }
+        }
+
+        for submission in &history.data {
+            if submission.verdict == Verdict::Correct {
+                stars.add(submission.day, submission.part);
+            }
+        }
+
+        stars
+    }
+
+    /// Reads the stars of a member of a private leaderboard.
+    pub fn from_member(member: &Member) -> Self {
+        let mut stars = Stars::default();
+
+        for (day, parts) in &member.days {
+            let Some(day) = Day::new(*day) else {
+                continue;
+            };
+            for (part, earned) in [1, 2].into_iter().zip(parts) {
+                if earned.is_some() {
+                    stars.add(day, part);
+                }
+            }
+        }
+
+        stars
+    }
+
+    pub fn count(&self) -> usize {
+        self.days
+            .values()
+            .flatten()
+            .filter(|solved| **solved)
+            .count()
+    }
+
+    fn add(&mut self, day: Day, part: u8) {
+        let index = match part {
+            1 => 0,
+            2 => 1,
+            _ => return,
+        };
+        self.days.entry(day).or_default()[index] = true;
+    }
+}
+
+fn construct_table(prefix: &str, year: u16, stars: &Stars) -> String {
+    let header = format!("{prefix} {year} Results");
+
+    let mut lines: Vec<String> = vec![
+        MARKER.into(),
+        header,
+        String::new(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    // days without stars before the last solved day are listed too, so gaps stay visible.
+    let last_day = stars.days.keys().next_back().copied();
+
+    for day in all_days().take_while(|day| Some(*day) <= last_day) {
+        let [part_1, part_2] = stars.days.get(&day).copied().unwrap_or_default();
+        let star = |solved: bool| if solved { "⭐" } else { " " };
+
+        lines.push(format!(
+            "| [Day {}](https://adventofcode.com/{year}/day/{}) | {} | {} |",
+            day.into_inner(),
+            day.into_inner(),
+            star(part_1),
+            star(part_2)
+        ));
+    }
+
+    lines.push(MARKER.into());
+
+    lines.join("\n")
+}
+
+fn update_content(s: &mut String, year: u16, stars: &Stars) -> Result<(), Error> {
+    let positions = locate_table(s, MARKER)?;
+    let table = construct_table("##", year, stars);
+    s.replace_range(positions.pos_start..positions.pos_end, &table);
+    Ok(())
+}
+
+pub fn update(year: u16, stars: &Stars) -> Result<(), Error> {
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    update_content(&mut readme, year, stars)?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{update_content, Stars, MARKER};
+    use crate::day;
+    use crate::template::answers::{Answers, DayAnswers};
+    use crate::template::leaderboard::Member;
+    use crate::template::submissions::{History, Submission, Verdict};
+    use crate::template::Day;
+
+    fn submission(day: u8, part: u8, verdict: Verdict) -> Submission {
+        Submission {
+            day: Day::new(day).unwrap(),
+            part,
+            answer: "42".into(),
+            time: 0,
+            verdict,
+        }
+    }
+
+    #[test]
+    fn reads_local_stars() {
+        let answers = Answers {
+            data: vec![DayAnswers {
+                day: day!(1),
+                part_1: Some("11".into()),
+                part_2: None,
+            }],
+        };
+        let history = History {
+            data: vec![
+                submission(1, 2, Verdict::Correct),
+                submission(3, 1, Verdict::TooHigh),
+                submission(3, 1, Verdict::Correct),
+                submission(4, 1, Verdict::Wrong),
+            ],
+        };
+
+        let stars = Stars::from_local(&answers, &history);
+        assert_eq!(
+            stars.days,
+            BTreeMap::from([(day!(1), [true, true]), (day!(3), [true, false])])
+        );
+        assert_eq!(stars.count(), 3);
+    }
+
+    #[test]
+    fn reads_member_stars() {
+        let member = Member {
+            id: 1,
+            name: None,
+            local_score: 0,
+            stars: 3,
+            days: BTreeMap::from([(2, [Some(10), Some(20)]), (5, [Some(30), None])]),
+        };
+
+        assert_eq!(
+            Stars::from_member(&member).days,
+            BTreeMap::from([(day!(2), [true, true]), (day!(5), [true, false])])
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_if_marker_not_present() {
+        let mut s = "# readme".to_string();
+        update_content(&mut s, 2023, &Stars::default()).unwrap();
+    }
+
+    #[test]
+    fn format_stars() {
+        let stars = Stars {
+            days: BTreeMap::from([(day!(1), [true, true]), (day!(3), [true, false])]),
+        };
+
+        let mut s = format!("foo\n{MARKER}\n\nbar");
+        update_content(&mut s, 2023, &stars).unwrap();
+        update_content(&mut s, 2023, &stars).unwrap();
+
+        let expected = [
+            "foo",
+            "<!--- advent_readme_stars table --->",
+            "## 2023 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](https://adventofcode.com/2023/day/1) | ⭐ | ⭐ |",
+            "| [Day 2](https://adventofcode.com/2023/day/2) |   |   |",
+            "| [Day 3](https://adventofcode.com/2023/day/3) | ⭐ |   |",
+            "<!--- advent_readme_stars table --->",
+            "",
+            "bar",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+}
@@ -0,0 +1,344 @@
+/// Machine-readable records that solution binaries emit for the runner.
+/// When a solution is invoked with `--report <path>`, every stage appends a JSON line to that file.
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    str::FromStr,
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::{cpu::CpuTime, memory::Memory, stats::Stats, timings::optional};
+
+/// The stage of a solution a record belongs to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stage {
+    Parse,
+    Part(u8),
+}
+
+impl Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Stage::Parse => write!(f, "Parse"),
+            Stage::Part(part) => write!(f, "Part {part}"),
+        }
+    }
+}
+
+/// The outcome of running a stage.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    /// The stage produced an answer.
+    Ok,
+    /// The part returned `None`.
+    NoAnswer,
+    /// The stage exceeded the configured time limit and was abandoned.
+    TimedOut,
+    /// The stage panicked.
+    Panicked,
+    /// The part returned an error.
+    Error,
+}
+
+impl Status {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::NoAnswer => "none",
+            Status::TimedOut => "timeout",
+            Status::Panicked => "panic",
+            Status::Error => "error",
+        }
+    }
+}
+
+impl FromStr for Status {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ok" => Ok(Status::Ok),
+            "none" => Ok(Status::NoAnswer),
+            "timeout" => Ok(Status::TimedOut),
+            "panic" => Ok(Status::Panicked),
+            "error" => Ok(Status::Error),
+            _ => Err(format!("Unknown record status `{s}`.")),
+        }
+    }
+}
+
+/// A single record, describing the result and timing of one stage.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Record {
+    pub stage: Stage,
+    pub answer: Option<String>,
+    pub nanos: f64,
+    pub samples: u64,
+    pub status: Status,
+    /// Details on why a stage failed, e.g. the panic message and location or the returned error.
+    pub message: Option<String>,
+    pub stats: Option<Stats>,
+    /// CPU time per execution, measured alongside the wall-clock time.
+    pub cpu: Option<CpuTime>,
+    pub memory: Option<Memory>,
+    /// Number of threads in the rayon thread pool the stage ran on.
+    pub threads: Option<u64>,
+}
+
+/// Appends a record as a single JSON line to the file at `path`.
+pub fn append(path: &Path, record: &Record) -> io::Result<()> {
+    let line = JsonValue::from(record)
+        .stringify()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{line}")
+}
+
+/// Reads all records from the file at `path`. A missing file yields no records.
+pub fn read(path: &Path) -> Result<Vec<Record>, String> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e.to_string()),
+    };
+
+    parse(&content)
+}
+
+/// Parses records from JSON lines, skipping empty lines.
+pub fn parse(content: &str) -> Result<Vec<Record>, String> {
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let json = JsonValue::from_str(line).or(Err("Expected record to be valid JSON."))?;
+            Record::try_from(&json)
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Record> for JsonValue {
+    fn from(value: &Record) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "part".into(),
+            match value.stage {
+                Stage::Parse => JsonValue::String("parse".into()),
+                Stage::Part(part) => JsonValue::Number(f64::from(part)),
+            },
+        );
+
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+
+        map.insert("nanos".into(), JsonValue::Number(value.nanos));
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert(
+            "status".into(),
+            JsonValue::String(value.status.as_str().into()),
+        );
+
+        map.insert(
+            "message".into(),
+            match &value.message {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+
+        map.insert(
+            "stats".into(),
+            match &value.stats {
+                Some(x) => JsonValue::from(x),
+                None => JsonValue::Null,
+            },
+        );
+
+        map.insert(
+            "cpu".into(),
+            match &value.cpu {
+                Some(x) => JsonValue::from(x),
+                None => JsonValue::Null,
+            },
+        );
+
+        map.insert(
+            "memory".into(),
+            match &value.memory {
+                Some(x) => JsonValue::from(x),
+                None => JsonValue::Null,
+            },
+        );
+
+        map.insert(
+            "threads".into(),
+            match value.threads {
+                Some(x) => JsonValue::Number(x as f64),
+                None => JsonValue::Null,
+            },
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Record {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected record to be a JSON object.")?;
+
+        let stage = match json.get("part") {
+            Some(JsonValue::String(s)) if s == "parse" => Stage::Parse,
+            Some(JsonValue::Number(n)) => Stage::Part(*n as u8),
+            _ => return Err("Expected record.part to be `parse` or a number.".into()),
+        };
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected record.answer to be null or string.")?;
+
+        let nanos = json
+            .get("nanos")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected record.nanos to be a number.")?;
+
+        let samples = json
+            .get("samples")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected record.samples to be a number.")?;
+
+        let status = json
+            .get("status")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected record.status to be a string.")?
+            .parse()?;
+
+        let message = optional(json, "record", "message", |v| {
+            v.get::<String>()
+                .cloned()
+                .ok_or_else(|| "Expected null or a string.".into())
+        })?;
+        let stats = optional(json, "record", "stats", |v| Stats::try_from(v))?;
+        let cpu = optional(json, "record", "cpu", |v| CpuTime::try_from(v))?;
+        let memory = optional(json, "record", "memory", |v| Memory::try_from(v))?;
+        let threads = optional(json, "record", "threads", |v| {
+            v.get::<f64>()
+                .map(|n| *n as u64)
+                .ok_or_else(|| "Expected null or a number.".into())
+        })?;
+
+        Ok(Record {
+            stage,
+            answer: answer.cloned(),
+            nanos,
+            samples: samples as u64,
+            status,
+            message,
+            stats,
+            cpu,
+            memory,
+            threads,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{parse, Record, Stage, Status};
+    use crate::template::{cpu::CpuTime, memory::Memory, stats::Stats};
+
+    #[test]
+    fn parses_records() {
+        let records = parse(
+            r#"{ "part": "parse", "answer": null, "nanos": 1500, "samples": 1, "status": "ok", "stats": null }
+
+{ "part": 1, "answer": "42", "nanos": 74.13, "samples": 100000, "status": "ok", "stats": null }
+{ "part": 2, "answer": null, "nanos": 10, "samples": 1, "status": "none" }"#,
+        )
+        .unwrap();
+
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].stage, Stage::Parse);
+        assert_eq!(records[1].stage, Stage::Part(1));
+        assert_eq!(records[1].answer, Some("42".into()));
+        assert_eq!(records[1].samples, 100_000);
+        assert_eq!(records[2].status, Status::NoAnswer);
+        assert_eq!(records[2].stats, None);
+        assert_eq!(records[2].cpu, None);
+        assert_eq!(records[2].memory, None);
+        assert_eq!(records[2].message, None);
+    }
+
+    #[test]
+    fn parses_panicked_records() {
+        let records = parse(
+            r#"{ "part": 1, "answer": null, "nanos": 0, "samples": 0, "status": "panic", "message": "oops at src/bin/01.rs:4:5" }"#,
+        )
+        .unwrap();
+
+        assert_eq!(records[0].status, Status::Panicked);
+        assert_eq!(records[0].message, Some("oops at src/bin/01.rs:4:5".into()));
+        assert_eq!(records[0].stage.to_string(), "Part 1");
+        assert_eq!(Stage::Parse.to_string(), "Parse");
+    }
+
+    #[test]
+    fn roundtrips_records() {
+        let record = Record {
+            stage: Stage::Part(2),
+            answer: Some("line 1\nline 2 with \"quotes\"".into()),
+            nanos: 1234.0,
+            samples: 10,
+            status: Status::Ok,
+            message: None,
+            stats: Some(Stats {
+                samples: 10,
+                median: 1234.0,
+                ..Stats::default()
+            }),
+            cpu: Some(CpuTime {
+                nanos: 4000.0,
+                ratio: 3.25,
+            }),
+            memory: Some(Memory {
+                peak_bytes: 1024,
+                allocations: 2,
+                allocated_bytes: 2048,
+            }),
+            threads: Some(8),
+        };
+
+        let line = JsonValue::from(&record).stringify().unwrap();
+        assert!(!line.contains('\n'));
+        assert_eq!(parse(&line).unwrap(), vec![record]);
+    }
+
+    #[test]
+    fn rejects_unknown_status() {
+        let res =
+            parse(r#"{ "part": 1, "answer": null, "nanos": 0, "samples": 1, "status": "?" }"#);
+        assert!(res.is_err());
+    }
+}
@@ -1,14 +1,27 @@
 use std::{collections::HashSet, io};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    report::{Stage, Status},
+    runner::RunOptions,
+    Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
 
 use super::{
     all_days,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+/// Run the solutions for a set of days, one after another.
+/// If `options.time` is set, each solution is benched and the collected timings are returned.
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    count_allocations: bool,
+    options: &RunOptions,
+) -> Option<Timings> {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut panicked: Vec<(Day, Vec<Stage>)> = vec![];
+    let mut errored: Vec<(Day, Vec<Stage>)> = vec![];
 
     let mut need_space = false;
 
@@ -24,17 +37,50 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
             println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
             println!("------");
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
-
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+            let records =
+                child_commands::run_solution(day, is_release, count_allocations, options).unwrap();
+
+            match records {
+                Some(records) if !records.is_empty() => {
+                    for (status, failed) in [
+                        (Status::Panicked, &mut panicked),
+                        (Status::Error, &mut errored),
+                    ] {
+                        let stages: Vec<Stage> = records
+                            .iter()
+                            .filter(|r| r.status == status)
+                            .map(|r| r.stage)
+                            .collect();
+
+                        if !stages.is_empty() {
+                            failed.push((day, stages));
+                        }
+                    }
+
+                    timings.push(Timing {
+                        bench: options.time.then(|| options.bench.clone()),
+                        ..Timing::from_records(day, &records)
+                    });
+                }
+                _ => println!("Not solved."),
             }
         });
 
-    if is_timed {
+    if !panicked.is_empty() {
+        println!(
+            "\n{ANSI_BOLD}Panicked:{ANSI_RESET} {}",
+            format_failed(&panicked)
+        );
+    }
+
+    if !errored.is_empty() {
+        println!(
+            "\n{ANSI_BOLD}Errored:{ANSI_RESET} {}",
+            format_failed(&errored)
+        );
+    }
+
+    if options.time {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
         println!(
@@ -46,10 +92,22 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
     }
 }
 
+/// Formats the failed stages of each day, e.g. `Day 03 (Part 1), Day 07 (Parse)`.
+fn format_failed(failed: &[(Day, Vec<Stage>)]) -> String {
+    failed
+        .iter()
+        .map(|(day, stages)| {
+            let stages: Vec<String> = stages.iter().map(ToString::to_string).collect();
+            format!("Day {day} ({})", stages.join(", "))
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
-    BrokenPipe,
+    Report(String),
     IO(io::Error),
 }
 
@@ -65,193 +123,86 @@ pub fn get_path_for_bin(day: Day) -> String {
 }
 
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as collecting their report records.
 pub mod child_commands {
     use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use crate::template::{
+        report::{self, Record},
+        runner::RunOptions,
+        Day,
+    };
     use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
-        thread,
+        env, fs,
+        path::{Path, PathBuf},
+        process::{self, Command, Stdio},
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Run the solution bin for a given day and collect the records it reports.
+    /// Returns `None` if the day has not been scaffolded yet.
+    pub fn run_solution(
+        day: Day,
+        is_release: bool,
+        count_allocations: bool,
+        options: &RunOptions,
+    ) -> Result<Option<Vec<Record>>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+            return Ok(None);
         }
 
+        let report_path = get_report_path(day);
+        let _ = fs::remove_file(&report_path);
+
         let day_padded = day.to_string();
+        let report_path_str = report_path.to_string_lossy().to_string();
         let mut args = vec!["run", "--quiet", "--bin", &day_padded];
 
         if is_release {
             args.push("--release");
         }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
-        }
-
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
-
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
-
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
-
-        let mut output = vec![];
-
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
-        });
-
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+        if count_allocations {
+            args.extend(["--features", "count-allocations"]);
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
-
-        Ok(output)
-    }
-
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
-
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
-
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
-
-                timings.total_nanos += nanos;
-            });
+        args.extend(["--", "--report", &report_path_str]);
 
-        timings
-    }
+        // mirror `--time`, bench settings and other run options to child invocations.
+        let option_args = options.to_args();
+        args.extend(option_args.iter().map(String::as_str));
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
+        // the solution prints human-readable output to stdout/stderr and writes records to the report file.
+        Command::new("cargo")
+            .args(&args)
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .status()?;
 
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
+        let records = report::read(&report_path).map_err(Error::Report);
+        let _ = fs::remove_file(&report_path);
 
-        Some((str_timing, parsed_timing))
+        records.map(Some)
     }
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+    fn get_report_path(day: Day) -> PathBuf {
+        env::temp_dir().join(format!("aoc-report-{day}-{}.jsonl", process::id()))
     }
+}
 
-    #[cfg(feature = "test_lib")]
-    mod tests {
-        use super::parse_exec_time;
-
-        use crate::day;
-
-        #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
-        }
-
-        #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
-        }
-
-        #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
-        }
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::format_failed;
+    use crate::{day, template::report::Stage};
+
+    #[test]
+    fn formats_failed_days() {
+        let failed = vec![
+            (day!(3), vec![Stage::Part(1), Stage::Part(2)]),
+            (day!(7), vec![Stage::Parse]),
+        ];
+        assert_eq!(
+            format_failed(&failed),
+            "Day 03 (Part 1, Part 2), Day 07 (Parse)"
+        );
     }
 }
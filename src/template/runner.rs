@@ -1,69 +1,606 @@
 /// Encapsulates code that interacts with solution functions.
+use std::any::Any;
+use std::cell::Cell;
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::num::NonZeroUsize;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::sync::{mpsc, Mutex, Once, OnceLock, PoisonError};
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{env, process, thread};
 
-use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use pico_args::Arguments;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
-    let part_str = format!("Part {part}");
+use crate::template::answers;
+use crate::template::aoc_client::SubmitOutcome;
+use crate::template::backend;
+use crate::template::bench::{parse_seconds, BenchSettings};
+use crate::template::confirm;
+use crate::template::cpu::{CpuTime, CpuTimer};
+use crate::template::examples::{self, Manifest};
+use crate::template::input::{InputSource, MissingInput};
+use crate::template::memory::{self, Memory};
+use crate::template::report::{self, Record, Stage, Status};
+use crate::template::session;
+use crate::template::stats::{histogram, nanos_to_duration, Stats};
+use crate::template::submissions::{Guard, History, Verdict};
+use crate::template::{Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{ANSI_BOLD, ANSI_GREEN, ANSI_RED};
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+const SOLUTION_STACK_SIZE: usize = 256 * 1024 * 1024;
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+static PANIC_HOOK: Once = Once::new();
+static PANIC_MESSAGE: Mutex<Option<String>> = Mutex::new(None);
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+static ARGS: OnceLock<RunnerArgs> = OnceLock::new();
+static THREAD_POOL: Once = Once::new();
+
+thread_local! {
+    static CATCHING_PANIC: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Options that control how a solution binary runs its stages.
+/// `solve`, `all` and `time` read them from the command line and forward them to the solution binaries.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RunOptions {
+    /// Abandon stages that run longer than this.
+    pub time_limit: Option<Duration>,
+    /// Bench each stage instead of running it once.
+    pub time: bool,
+    pub bench: BenchSettings,
+    /// Number of threads in the global rayon thread pool. Defaults to the number of CPUs.
+    pub threads: Option<usize>,
+    /// Measure each part on a single thread as well and report the speedup.
+    pub speedup: bool,
+}
+
+impl RunOptions {
+    /// Reads `--time-limit <seconds>`, `--time`, `--threads <n>`, `--speedup` and the bench settings.
+    /// Bench settings are accepted without `--time`, but only take effect with it.
+    pub fn from_args(args: &mut Arguments) -> Result<Self, pico_args::Error> {
+        Ok(RunOptions {
+            time_limit: args.opt_value_from_fn("--time-limit", parse_seconds)?,
+            time: args.contains("--time"),
+            bench: BenchSettings::from_args(args)?,
+            threads: args
+                .opt_value_from_str::<_, NonZeroUsize>("--threads")?
+                .map(NonZeroUsize::get),
+            speedup: args.contains("--speedup"),
+        })
+    }
+
+    /// Formats the options as arguments for a solution binary, the inverse of [`RunOptions::from_args`].
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
+
+        if let Some(time_limit) = self.time_limit {
+            args.extend(["--time-limit".into(), time_limit.as_secs_f64().to_string()]);
+        }
+
+        if self.time {
+            args.push("--time".into());
+            args.extend(self.bench.to_args());
+        }
+
+        if let Some(threads) = self.threads {
+            args.extend(["--threads".into(), threads.to_string()]);
+        }
+
+        if self.speedup {
+            args.push("--speedup".into());
+        }
+
+        args
+    }
+}
+
+/// Arguments that `solve`, `all` and `time` pass to solution binaries.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct RunnerArgs {
+    /// Path of the JSON lines file that records are appended to.
+    report: Option<PathBuf>,
+    submit: Option<u8>,
+    input: InputSource,
+    /// Download a missing or empty puzzle input without asking.
+    download: bool,
+    options: RunOptions,
+}
+
+impl RunnerArgs {
+    fn parse(mut args: Arguments) -> Result<Self, pico_args::Error> {
+        let runner_args = RunnerArgs {
+            report: args.opt_value_from_os_str("--report", |s| {
+                Ok::<_, pico_args::Error>(PathBuf::from(s))
+            })?,
+            submit: args.opt_value_from_str("--submit")?,
+            input: InputSource::from_args(&mut args)?,
+            download: args.contains("--download"),
+            options: RunOptions::from_args(&mut args)?,
+        };
+
+        let remaining = args.finish();
+        if !remaining.is_empty() {
+            eprintln!("Warning: unknown argument(s): {remaining:?}.");
+        }
+
+        Ok(runner_args)
+    }
+}
+
+/// Returns the arguments the solution binary was invoked with, parsing them on first access.
+fn args() -> &'static RunnerArgs {
+    ARGS.get_or_init(|| match RunnerArgs::parse(Arguments::from_env()) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            process::exit(1);
+        }
+    })
+}
+
+/// Configures the global rayon thread pool before the first stage runs.
+fn init_thread_pool() {
+    THREAD_POOL.call_once(|| {
+        if let Some(threads) = args().options.threads {
+            if let Err(e) = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build_global()
+            {
+                eprintln!("Failed to configure the thread pool: {e}");
+            }
+        }
+    });
+}
+
+/// Read the input selected with `--example`, `--example-part <n>` or `--input <path>`, defaulting to the puzzle input.
+/// Other inputs than the puzzle input are labeled in the output. Exits if the input cannot be read.
+/// A missing or empty puzzle input is downloaded with `--download`, otherwise the user is asked, unless the day runs as part of `all` or `time`.
+///
+/// Parts may outlive `main` if they exceed the time limit, so the input is leaked.
+pub fn read_input(day: Day) -> &'static str {
+    let source = &args().input;
+    let missing = if args().download {
+        MissingInput::Download
+    } else if args().report.is_some() {
+        MissingInput::Fail
+    } else {
+        MissingInput::Ask
+    };
+
+    let input = match source.read_or_download(day, missing) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    if *source != InputSource::Puzzle {
+        println!("{ANSI_ITALIC}Input: {source}{ANSI_RESET}");
+    }
+
+    Box::leak(input.into_boxed_str())
+}
+
+/// The value returned by a solution part.
+/// Parts return either an `Option`, where `None` means "no answer", or a `Result` whose error explains why there is no answer.
+pub trait Answer: Send + 'static {
+    fn outcome(&self) -> Outcome;
+}
+
+/// A part's return value, converted for printing and recording.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Answer(String),
+    NoAnswer,
+    /// Contains the formatted error, including its chain of causes if the error type prints them.
+    Error(String),
+}
+
+impl<T: Display + Send + 'static> Answer for Option<T> {
+    fn outcome(&self) -> Outcome {
+        match self {
+            Some(answer) => Outcome::Answer(answer.to_string()),
+            None => Outcome::NoAnswer,
+        }
+    }
+}
+
+impl<T: Display + Send + 'static, E: Display + Send + 'static> Answer for Result<T, E> {
+    fn outcome(&self) -> Outcome {
+        match self {
+            Ok(answer) => Outcome::Answer(answer.to_string()),
+            // the alternate format prints the full chain of causes for `anyhow`-style errors.
+            Err(e) => Outcome::Error(format!("{e:#}")),
+        }
+    }
+}
+
+/// Run a solution part and print its result.
+/// If the part panics, the failure is printed and recorded, and the caller continues with the next part.
+/// If it exceeds the time limit, the day is abandoned, see [`abandon`].
+pub fn run_part<I, R, F>(func: F, input: I, day: Day, part: u8)
+where
+    I: Clone + Send + 'static,
+    R: Answer,
+    F: Fn(I) -> R + Copy + Send + 'static,
+{
+    init_thread_pool();
+
+    let stage = Stage::Part(part);
+    let part_str = stage.to_string();
+
+    let speedup_input = args().options.speedup.then(|| input.clone());
+
+    let measurement = match run_timed(func, input, |result| {
+        print_result(&result.outcome(), &part_str, "");
+    }) {
+        Ok(measurement) => measurement,
+        Err(failure @ Failure::TimedOut(_)) => abandon(stage, &failure),
+        Err(failure) => {
+            print_failure(&part_str, &failure);
+            write_record(&failure_record(stage, &failure));
+            return;
+        }
+    };
+
+    let outcome = measurement.result.outcome();
+
+    print_result(&outcome, &part_str, &measurement.format());
+    print_example_check(&outcome, day, part);
+    print_report(measurement.report.as_ref());
+
+    if let Some(input) = speedup_input {
+        print_speedup(measurement.duration, measure_single_threaded(func, input));
+    }
+
+    let (answer, status, message) = match &outcome {
+        Outcome::Answer(answer) => (Some(answer.clone()), Status::Ok, None),
+        Outcome::NoAnswer => (None, Status::NoAnswer, None),
+        Outcome::Error(e) => (None, Status::Error, Some(e.clone())),
+    };
+
+    write_record(&Record {
+        stage,
+        answer,
+        status,
+        message,
+        ..measurement.to_record()
+    });
+
+    match outcome {
+        Outcome::Answer(answer) => submit_result(&answer, day, part),
+        Outcome::Error(_) if args().submit == Some(part) => {
+            eprintln!("Not submitting part {part}, the solution returned an error.");
+        }
+        _ => {}
+    }
+}
+
+/// Run the parse stage shared by both parts of a solution and return its output.
+/// Parsing is timed (and benched) separately so its cost does not show up in the part timings.
+///
+/// If parsing panics or exceeds the time limit, the parts cannot run and the process exits.
+pub fn run_parse<I, T, F>(func: F, input: I) -> T
+where
+    I: Clone + Send + 'static,
+    T: Send + 'static,
+    F: Fn(I) -> T + Copy + Send + 'static,
+{
+    init_thread_pool();
+
+    let measurement = match run_timed(func, input, |_| print!("Parse:")) {
+        Ok(measurement) => measurement,
+        Err(failure) => abandon(Stage::Parse, &failure),
+    };
+
+    print!("\r");
+    println!("Parse:{}", measurement.format());
+    print_report(measurement.report.as_ref());
+
+    write_record(&Record {
+        stage: Stage::Parse,
+        ..measurement.to_record()
+    });
+
+    measurement.result
+}
+
+/// Print and record a stage that timed out, then exit without running the remaining stages.
+/// The abandoned thread keeps running until the process exits, so it would skew the measurements of any later stage.
+fn abandon(stage: Stage, failure: &Failure) -> ! {
+    print_failure(&stage.to_string(), failure);
+    write_record(&failure_record(stage, failure));
+    println!("Skipping the remaining stages.");
+    process::exit(1);
+}
+
+/// Why a solution stage did not produce a result.
+enum Failure {
+    TimedOut(Duration),
+    /// Contains the panic message and location.
+    Panicked(String),
+}
+
+/// The result of running a solution stage, along with everything that was measured while running it.
+struct Measurement<T> {
+    result: T,
+    duration: Duration,
+    samples: u128,
+    report: Option<(Stats, String)>,
+    cpu: Option<CpuTime>,
+    memory: Option<Memory>,
+}
+
+impl<T> Measurement<T> {
+    fn format(&self) -> String {
+        let mut formatted = format_duration(&self.duration, self.samples);
+
+        if let Some(cpu) = &self.cpu {
+            formatted.push_str(&format!(" [{}]", cpu.summary()));
+        }
+
+        if let Some(memory) = &self.memory {
+            formatted.push_str(&format!(" [{}]", memory.summary()));
+        }
+
+        formatted
+    }
+
+    /// Creates a record from the measured values, the caller fills in stage, answer and status.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    fn to_record(&self) -> Record {
+        Record {
+            stage: Stage::Parse,
+            answer: None,
+            nanos: self.duration.as_nanos() as f64,
+            samples: self.samples as u64,
+            status: Status::Ok,
+            message: None,
+            stats: self.report.as_ref().map(|(stats, _)| stats.clone()),
+            cpu: self.cpu.clone(),
+            memory: self.memory.clone(),
+            threads: Some(rayon::current_num_threads() as u64),
+        }
     }
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Clone, T>(
+///  2. with `--time`, the function is benched according to the bench settings (by default approx. 1 second of execution time or 10 samples, whatever take longer.)
+///     The reported duration is the median of all samples, a full statistical report is returned alongside it.
+///
+/// CPU time is measured alongside wall-clock time, across all samples when benching.
+/// Heap usage is measured during the first execution if the `count-allocations` feature is enabled.
+/// If a time limit is configured and the first execution exceeds it, the execution is abandoned.
+/// Benching stops early once it runs longer than the time limit.
+/// If the first execution panics, the panic is caught and returned as error.
+fn run_timed<I, T, F>(func: F, input: I, hook: impl Fn(&T)) -> Result<Measurement<T>, Failure>
+where
+    I: Clone + Send + 'static,
+    T: Send + 'static,
+    F: Fn(I) -> T + Copy + Send + 'static,
+{
+    let (result, base_time, base_cpu, memory) = match time_limit() {
+        Some(limit) => run_with_limit(func, input.clone(), limit)?,
+        None => run_once(func, input.clone()).map_err(Failure::Panicked)?,
+    };
+
+    hook(&result);
+
+    if args().options.time {
+        let settings = &args().options.bench;
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout().flush();
+
+        let (stats, histogram, cpu) = bench(func, input, &base_time, settings, time_limit());
+        Ok(Measurement {
+            result,
+            duration: nanos_to_duration(stats.median),
+            samples: u128::from(stats.samples),
+            report: Some((stats, histogram)),
+            cpu,
+            memory,
+        })
+    } else {
+        Ok(Measurement {
+            result,
+            duration: base_time,
+            samples: 1,
+            report: None,
+            cpu: base_cpu.map(|cpu| CpuTime::from_measurement(cpu, base_time, 1)),
+            memory,
+        })
+    }
+}
+
+fn run_once<I, T>(
     func: impl Fn(I) -> T,
     input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
-    let timer = Instant::now();
-    let result = {
-        let input = input.clone();
+) -> Result<(T, Duration, Option<Duration>, Option<Memory>), String> {
+    memory::reset();
 
+    let cpu_timer = CpuTimer::start();
+    let timer = Instant::now();
+    let result = catch_panic(|| {
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
         func(input)
-    };
+    })?;
     let base_time = timer.elapsed();
+    let cpu_time = cpu_timer.elapsed();
 
-    hook(&result);
+    Ok((result, base_time, cpu_time, memory::snapshot()))
+}
+
+/// Run the function and catch a panic, returning the panic message and location as error.
+/// While a panic is caught, the default panic output is suppressed, panics outside of solutions are reported as usual.
+pub(crate) fn catch_panic<T>(func: impl FnOnce() -> T) -> Result<T, String> {
+    PANIC_HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if CATCHING_PANIC.get() {
+                let location = info
+                    .location()
+                    .map(|l| format!(" at {}:{}:{}", l.file(), l.line(), l.column()))
+                    .unwrap_or_default();
+                let message = format!("{}{location}", panic_message(info.payload()));
+                *PANIC_MESSAGE.lock().unwrap_or_else(PoisonError::into_inner) = Some(message);
+            } else {
+                default_hook(info);
+            }
+        }));
+    });
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    CATCHING_PANIC.set(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(func));
+    CATCHING_PANIC.set(false);
+
+    result.map_err(|payload| {
+        // panics on other threads (e.g. rayon workers) are re-raised without passing the hook on this thread.
+        PANIC_MESSAGE
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+            .unwrap_or_else(|| panic_message(payload.as_ref()))
+    })
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
     } else {
-        (base_time, 1)
+        "Box<dyn Any>".into()
+    }
+}
+
+/// Run the function on a separate thread and wait for at most `limit` for it to finish.
+/// A thread that exceeds the limit is abandoned, it is stopped once the process exits.
+fn run_with_limit<I, T, F>(
+    func: F,
+    input: I,
+    limit: Duration,
+) -> Result<(T, Duration, Option<Duration>, Option<Memory>), Failure>
+where
+    I: Send + 'static,
+    T: Send + 'static,
+    F: Fn(I) -> T + Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+
+    // solutions can recurse deeply, give the thread a generous stack.
+    let spawned = thread::Builder::new()
+        .stack_size(SOLUTION_STACK_SIZE)
+        .spawn(move || {
+            let _ = sender.send(run_once(func, input));
+        });
+
+    if let Err(e) = spawned {
+        eprintln!("Failed to spawn solution thread: {e}");
+        process::exit(1);
+    }
+
+    match receiver.recv_timeout(limit) {
+        Ok(result) => result.map_err(Failure::Panicked),
+        Err(_) => Err(Failure::TimedOut(limit)),
+    }
+}
+
+/// Returns the time limit for a single part, if configured.
+/// The `--time-limit <seconds>` argument takes precedence over the `AOC_TIME_LIMIT` environment variable.
+fn time_limit() -> Option<Duration> {
+    if let Some(limit) = args().options.time_limit {
+        return Some(limit);
+    }
+
+    let value = env::var("AOC_TIME_LIMIT").ok()?;
+
+    match parse_seconds(&value) {
+        Ok(limit) => Some(limit),
+        Err(e) => {
+            eprintln!("Ignoring AOC_TIME_LIMIT: {e}");
+            None
+        }
+    }
+}
+
+fn failure_record(stage: Stage, failure: &Failure) -> Record {
+    let (nanos, status, message) = match failure {
+        Failure::TimedOut(limit) => (
+            limit.as_secs_f64() * 1_000_000_000_f64,
+            Status::TimedOut,
+            None,
+        ),
+        Failure::Panicked(message) => (0.0, Status::Panicked, Some(message.clone())),
     };
 
-    (result, run.0, run.1)
+    Record {
+        stage,
+        answer: None,
+        nanos,
+        samples: 0,
+        status,
+        message,
+        stats: None,
+        cpu: None,
+        memory: None,
+        threads: Some(rayon::current_num_threads() as u64),
+    }
+}
+
+fn print_failure(part: &str, failure: &Failure) {
+    print!("\r");
+    match failure {
+        Failure::TimedOut(limit) => println!("{part}: ✖ timed out after {}", format_limit(*limit)),
+        Failure::Panicked(message) => println!("{part}: ✖ panicked: {message}"),
+    }
+}
+
+/// Formats a time limit in seconds, e.g. `10s` or `0.5s`.
+pub fn format_limit(limit: Duration) -> String {
+    format!("{}s", limit.as_secs_f64())
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    settings: &BenchSettings,
+    limit: Option<Duration>,
+) -> (Stats, String, Option<CpuTime>) {
+    let bench_iterations = settings.samples(*base_time);
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+    // the time limit caps the whole bench, including warmup.
+    let deadline = limit.map(|limit| Instant::now() + limit);
+    let expired = || deadline.is_some_and(|deadline| Instant::now() >= deadline);
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    // warm up caches and the branch predictor before taking measurements.
+    let mut warmup_iterations = settings.warmup_iterations(bench_iterations);
+
+    for i in 0..warmup_iterations {
+        if expired() {
+            warmup_iterations = i;
+            break;
+        }
+        black_box(func(black_box(input.clone())));
+    }
 
     let mut timers: Vec<Duration> = vec![];
 
+    let cpu_timer = CpuTimer::start();
+    let wall_timer = Instant::now();
+
     for _ in 0..bench_iterations {
+        // keep at least one sample, even if warmup used up the time limit.
+        if !timers.is_empty() && expired() {
+            break;
+        }
+
         // need a clone here to make the borrow checker happy.
         let cloned = input.clone();
         let timer = Instant::now();
@@ -71,19 +608,106 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    let cpu = cpu_timer
+        .elapsed()
+        .map(|cpu| CpuTime::from_measurement(cpu, wall_timer.elapsed(), timers.len() as u64));
+
+    let stats = Stats::from_samples(&timers, warmup_iterations)
+        .expect("bench should collect at least one sample");
+
+    (stats, histogram(&timers), cpu)
+}
+
+/// Measure the function on a single-threaded rayon pool, to compare it with the measurement on the global pool.
+/// Benches with the same settings if `--time` is set, otherwise runs the function once.
+fn measure_single_threaded<I, T, F>(func: F, input: I) -> Duration
+where
+    I: Clone + Send,
+    T: Send,
+    F: Fn(I) -> T + Send,
+{
+    let pool = match rayon::ThreadPoolBuilder::new().num_threads(1).build() {
+        Ok(pool) => pool,
+        Err(e) => {
+            eprintln!("Failed to create a single-threaded pool: {e}");
+            process::exit(1);
+        }
+    };
+
+    pool.install(move || {
+        let timer = Instant::now();
+        black_box(func(black_box(input.clone())));
+        let base_time = timer.elapsed();
+
+        if args().options.time {
+            nanos_to_duration(
+                bench(func, input, &base_time, &args().options.bench, time_limit())
+                    .0
+                    .median,
+            )
+        } else {
+            base_time
+        }
+    })
+}
+
+fn print_speedup(duration: Duration, single_threaded: Duration) {
+    let threads = rayon::current_num_threads();
+    let speedup = single_threaded.as_secs_f64() / duration.as_secs_f64().max(f64::EPSILON);
+    let unit = if threads == 1 { "thread" } else { "threads" };
+    println!("  speedup ×{speedup:.2} on {threads} {unit} (1 thread: {single_threaded:.1?})");
+}
+
+/// When running on an example, compare the outcome with the answer listed in the example manifest and print whether it matches.
+fn print_example_check(outcome: &Outcome, day: Day, part: u8) {
+    let source = &args().input;
+    if !matches!(source, InputSource::Example(_)) {
+        return;
+    }
+
+    let manifest = match Manifest::read(day) {
+        Ok(Some(manifest)) => manifest,
+        Ok(None) => return,
+        Err(e) => {
+            eprintln!("{e}");
+            return;
+        }
+    };
+
+    let file = source.path(day).and_then(|path| {
+        path.file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+    });
+
+    let Some(expected) = file
+        .and_then(|file| manifest.find(&file))
+        .and_then(|example| example.answer(part))
+    else {
+        return;
+    };
+
+    match examples::mismatch(outcome, expected) {
+        None => println!("  {ANSI_GREEN}✔ matches the example answer{ANSI_RESET}"),
+        Some(mismatch) => println!("  {ANSI_RED}✖ {mismatch}{ANSI_RESET}"),
+    }
+}
+
+/// Append a record to the report file if the solution was invoked with `--report <path>`.
+fn write_record(record: &Record) {
+    let Some(path) = &args().report else {
+        return;
+    };
+
+    if let Err(e) = report::append(path, record) {
+        eprintln!("Failed to write report record: {e}");
+    }
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+fn print_report(report: Option<&(Stats, String)>) {
+    if let Some((stats, histogram)) = report {
+        println!("  {}", stats.summary());
+        println!("  {histogram}");
+    }
 }
 
 fn format_duration(duration: &Duration, samples: u128) -> String {
@@ -94,12 +718,12 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result(outcome: &Outcome, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
-    match result {
-        Some(result) => {
-            if result.to_string().contains('\n') {
+    match outcome {
+        Outcome::Answer(result) => {
+            if result.contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
                     print!("{str}");
@@ -118,7 +742,7 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             }
         }
-        None => {
+        Outcome::NoAnswer => {
             if is_intermediate_result {
                 print!("{part}: ✖");
             } else {
@@ -126,44 +750,239 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 println!("{part}: ✖             ");
             }
         }
+        Outcome::Error(e) => {
+            if is_intermediate_result {
+                print!("{part}: ✖");
+            } else {
+                print!("\r");
+                println!("{part}: ✖ error: {e}{duration_str}");
+            }
+        }
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
+/// Try to submit one part of the solution if:
+///  1. the part was selected with `--submit <part>`.
+///  2. the solution ran on the puzzle input.
+///  3. the solution ran as the default account, not as another account in `AOC_ACCOUNT`.
+///  4. the submission history does not rule out the answer, and suspicious answers are confirmed.
+///  5. the backend selected by `AOC_BACKEND` can be set up, e.g. aoc-cli is installed for `aoc-cli`.
+///
+/// The verdict is recorded in the submission history, correct answers are added to the known answers.
+fn submit_result(answer: &str, day: Day, part: u8) {
+    if args().submit != Some(part) {
+        return;
+    }
 
-    if !args.contains(&"--submit".into()) {
-        return None;
+    if args().input != InputSource::Puzzle {
+        eprintln!(
+            "Not submitting part {part}, only answers for the puzzle input can be submitted (input: {}).",
+            args().input
+        );
+        return;
     }
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    // the solution ran on the default account's input, and the history in `data` is that account's.
+    match session::account_from_env() {
+        Ok(None) => {}
+        Ok(Some(account)) => {
+            eprintln!("Not submitting part {part} as account `{account}`, only the default account can submit answers.");
+            return;
+        }
+        Err(e) => {
+            eprintln!("Not submitting part {part}, {e}");
+            return;
+        }
     }
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+    let mut history = match History::read_from_file() {
+        Ok(history) => history,
+        Err(e) => {
+            eprintln!("Not submitting part {part}, failed to read the submission history: {e}");
+            return;
+        }
+    };
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    match history.guard(day, part, answer) {
+        Guard::Allow => {}
+        Guard::Refuse(reason) => {
+            eprintln!("Not submitting part {part}, {reason}");
+            return;
+        }
+        Guard::Confirm(reason) => {
+            if !confirm(&format!("{reason} Submit part {part} anyway?"), false) {
+                return;
+            }
+        }
+    }
+
+    let backend = match backend::from_env() {
+        Ok(backend) => backend,
+        Err(e) => {
+            eprintln!("Failed to submit: {e}");
+            return;
+        }
+    };
+
+    println!("Submitting result...");
+    let response = match backend.submit(day, part, answer) {
+        Ok(response) => response,
+        Err(e) => {
+            eprintln!("Failed to submit: {e}");
+            return;
+        }
+    };
+
+    print!("{}", response.message);
+
+    let verdict = match response.outcome {
+        SubmitOutcome::Judged(verdict) => verdict,
+        SubmitOutcome::TooRecent(_) | SubmitOutcome::WrongLevel | SubmitOutcome::Unknown => {
+            eprintln!(
+                "The response did not judge the answer, it is not recorded in the submission history."
+            );
+            return;
+        }
     };
 
-    if part_submit != part {
-        return None;
+    history.record(day, part, answer, verdict);
+    if let Err(e) = history.store_file() {
+        eprintln!("Failed to save the submission history: {e}");
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    if verdict == Verdict::Correct {
+        match answers::record_correct(day, part, answer) {
+            Ok(path) => println!("Saved the answer to {}.", path.display()),
+            Err(e) => eprintln!("Failed to save the answer: {e}"),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{ffi::OsString, fmt, path::PathBuf, time::Duration};
+
+    use pico_args::Arguments;
+
+    use super::{Answer, Outcome, RunOptions, RunnerArgs};
+    use crate::template::{bench::BenchSettings, input::InputSource};
+
+    /// Mimics errors that print their causes with the alternate format, such as `anyhow::Error`.
+    struct ChainedError;
+
+    impl fmt::Display for ChainedError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            if f.alternate() {
+                write!(f, "invalid grid: unexpected character `x`")
+            } else {
+                write!(f, "invalid grid")
+            }
+        }
+    }
+
+    #[test]
+    fn converts_options() {
+        assert_eq!(Some(42).outcome(), Outcome::Answer("42".into()));
+        assert_eq!(None::<u32>.outcome(), Outcome::NoAnswer);
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    #[test]
+    fn converts_results() {
+        assert_eq!(
+            Ok::<_, String>("abc").outcome(),
+            Outcome::Answer("abc".into())
+        );
+        assert_eq!(
+            Err::<u32, _>("no path found").outcome(),
+            Outcome::Error("no path found".into())
+        );
+        assert_eq!(
+            Err::<u32, _>(ChainedError).outcome(),
+            Outcome::Error("invalid grid: unexpected character `x`".into())
+        );
+    }
+
+    #[test]
+    fn converts_boxed_errors() {
+        let res: Result<u32, Box<dyn std::error::Error + Send + Sync>> =
+            "x".parse::<u32>().map_err(Into::into);
+        assert_eq!(
+            res.outcome(),
+            Outcome::Error("invalid digit found in string".into())
+        );
+    }
+
+    fn parse_args(args: &[&str]) -> Result<RunnerArgs, pico_args::Error> {
+        RunnerArgs::parse(Arguments::from_vec(
+            args.iter().map(OsString::from).collect(),
+        ))
+    }
+
+    #[test]
+    fn parses_runner_args() {
+        assert_eq!(parse_args(&[]).unwrap(), RunnerArgs::default());
+
+        let args = parse_args(&[
+            "--report",
+            "/tmp/report.jsonl",
+            "--time",
+            "--time-limit",
+            "0.5",
+            "--submit",
+            "2",
+            "--max-samples",
+            "100",
+            "--example-part",
+            "2",
+            "--download",
+        ])
+        .unwrap();
+
+        assert_eq!(
+            args,
+            RunnerArgs {
+                report: Some(PathBuf::from("/tmp/report.jsonl")),
+                submit: Some(2),
+                input: InputSource::Example(Some(2)),
+                download: true,
+                options: RunOptions {
+                    time_limit: Some(Duration::from_millis(500)),
+                    time: true,
+                    bench: BenchSettings {
+                        max_samples: 100,
+                        ..BenchSettings::default()
+                    },
+                    threads: None,
+                    speedup: false,
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_runner_args() {
+        assert!(parse_args(&["--submit", "x"]).is_err());
+        assert!(parse_args(&["--time-limit", "0"]).is_err());
+        assert!(parse_args(&["--threads", "0"]).is_err());
+    }
+
+    #[test]
+    fn roundtrips_run_options() {
+        let options = RunOptions {
+            time_limit: Some(Duration::from_millis(1500)),
+            time: true,
+            bench: BenchSettings {
+                warmup: Some(2),
+                ..BenchSettings::default()
+            },
+            threads: Some(4),
+            speedup: true,
+        };
+        let args: Vec<String> = options.to_args();
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        assert_eq!(parse_args(&args).unwrap().options, options);
+        assert!(RunOptions::default().to_args().is_empty());
+    }
 }
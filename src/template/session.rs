@@ -0,0 +1,211 @@
+/// Finds the session cookies of Advent of Code accounts, and explains how to refresh them once they expire.
+/// The default account is read from `ADVENT_OF_CODE_SESSION` or `~/.adventofcode.session`, like aoc-cli does.
+/// Named accounts, e.g. of team members, are read from `ADVENT_OF_CODE_SESSION_<NAME>` or `~/.adventofcode.<name>.session`, and selected with `AOC_ACCOUNT`.
+use std::{
+    env, fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+const SESSION_ENV: &str = "ADVENT_OF_CODE_SESSION";
+
+pub const COPY_INSTRUCTIONS: &str = "Log in on https://adventofcode.com, open the developer tools with F12 and copy the value of the `session` cookie under Application or Storage > Cookies.";
+
+/// The account selected by `AOC_ACCOUNT`, `None` for the default account.
+pub fn account_from_env() -> Result<Option<String>, String> {
+    match env::var("AOC_ACCOUNT") {
+        Ok(account) if !account.trim().is_empty() => {
+            check_account(account.trim()).map(|()| Some(account.trim().to_string()))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Account names become part of file names, so they are limited to letters, digits, `-` and `_`.
+pub fn check_account(account: &str) -> Result<(), String> {
+    if !account.is_empty()
+        && account
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid account name `{account}`, use only letters, digits, `-` and `_`."
+        ))
+    }
+}
+
+pub fn describe(account: Option<&str>) -> String {
+    match account {
+        Some(account) => format!("account `{account}`"),
+        None => "the default account".into(),
+    }
+}
+
+/// Where the downloads of an account are kept: `data` for the default account, `data/accounts/<name>` for named accounts.
+pub fn data_dir(account: Option<&str>) -> PathBuf {
+    match account {
+        Some(account) => Path::new("data").join("accounts").join(account),
+        None => PathBuf::from("data"),
+    }
+}
+
+/// The environment variable that holds the session cookie of an account.
+pub fn env_var(account: Option<&str>) -> String {
+    match account {
+        Some(account) => format!("{SESSION_ENV}_{}", account.to_uppercase().replace('-', "_")),
+        None => SESSION_ENV.into(),
+    }
+}
+
+fn file_name(account: Option<&str>) -> String {
+    match account {
+        Some(account) => format!(".adventofcode.{account}.session"),
+        None => ".adventofcode.session".into(),
+    }
+}
+
+fn home_dir() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+}
+
+/// The file that a refreshed session cookie is written to, `~/.adventofcode.session` for the default account.
+pub fn session_path(account: Option<&str>) -> Option<PathBuf> {
+    home_dir().map(|home| home.join(file_name(account)))
+}
+
+/// Reads the session cookie of an account from its environment variable, or from its file in the home or config directory.
+pub fn read(account: Option<&str>) -> Option<String> {
+    if let Ok(session) = env::var(env_var(account)) {
+        return Some(session.trim().to_string()).filter(|s| !s.is_empty());
+    }
+
+    let home = home_dir()?;
+    let config = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| home.join(".config"));
+    let name = file_name(account);
+
+    [home.join(&name), config.join(&name[1..])]
+        .iter()
+        .find_map(|path| fs::read_to_string(path).ok())
+        .map(|session| session.trim().to_string())
+        .filter(|session| !session.is_empty())
+}
+
+/// Checks that a session cookie looks like one, before it is sent anywhere.
+/// Session cookies are long hexadecimal strings. Pasting the whole cookie, e.g. `session=53616c74...`, is allowed.
+pub fn check_format(session: &str) -> Result<(), String> {
+    let session = strip_name(session);
+
+    if session.len() < 32 || !session.chars().all(|c| c.is_ascii_hexdigit()) {
+        Err(
+            "The session cookie should be a long hexadecimal string, e.g. `53616c7465645f5f…`."
+                .into(),
+        )
+    } else {
+        Ok(())
+    }
+}
+
+pub fn strip_name(session: &str) -> &str {
+    let session = session.trim();
+    session.strip_prefix("session=").unwrap_or(session)
+}
+
+/// Lists the named accounts that have a session cookie in the home directory or the environment.
+pub fn accounts() -> Vec<String> {
+    let prefix = format!("{SESSION_ENV}_");
+    let mut accounts: Vec<String> = env::vars()
+        .filter_map(|(key, _)| key.strip_prefix(&prefix).map(str::to_lowercase))
+        .collect();
+
+    if let Some(entries) = home_dir().and_then(|home| fs::read_dir(home).ok()) {
+        accounts.extend(entries.filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            let account = name
+                .strip_prefix(".adventofcode.")?
+                .strip_suffix(".session")?;
+            check_account(account).ok().map(|()| account.to_string())
+        }));
+    }
+
+    accounts.sort();
+    accounts.dedup();
+    accounts
+}
+
+/// Writes a session cookie to the file of an account, readable only by the user.
+pub fn store(account: Option<&str>, session: &str) -> io::Result<PathBuf> {
+    let path = session_path(account)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no home directory."))?;
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    let mut file = options.open(&path)?;
+
+    // the mode only applies to new files, an existing file is narrowed before the cookie is written to it.
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    }
+
+    writeln!(file, "{}", strip_name(session))?;
+
+    Ok(path)
+}
+
+/// Explains how to get a new session cookie for an account.
+pub fn refresh_instructions(account: Option<&str>) -> String {
+    let flag = account.map_or_else(String::new, |account| format!(" --account {account}"));
+    let path =
+        session_path(account).map_or_else(|| file_name(account), |path| path.display().to_string());
+
+    format!(
+        "To get a fresh one: {COPY_INSTRUCTIONS} Then run `cargo session --refresh{flag}` and paste it, or write it to \"{path}\"."
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{check_account, check_format, data_dir, env_var, strip_name};
+
+    #[test]
+    fn checks_session_formats() {
+        let session = "53616c7465645f5f".repeat(8);
+
+        assert_eq!(check_format(&session), Ok(()));
+        assert_eq!(check_format(&format!("session={session}\n")), Ok(()));
+        assert!(check_format("").is_err());
+        assert!(check_format("abc").is_err());
+        assert!(check_format(&format!("{session}!")).is_err());
+        assert_eq!(strip_name(" session=abc "), "abc");
+    }
+
+    #[test]
+    fn names_accounts() {
+        assert_eq!(check_account("bob_2-x"), Ok(()));
+        assert!(check_account("../bob").is_err());
+        assert!(check_account("").is_err());
+
+        assert_eq!(env_var(None), "ADVENT_OF_CODE_SESSION");
+        assert_eq!(env_var(Some("bob-2")), "ADVENT_OF_CODE_SESSION_BOB_2");
+        assert_eq!(data_dir(None), PathBuf::from("data"));
+        assert_eq!(data_dir(Some("bob")), PathBuf::from("data/accounts/bob"));
+    }
+}
@@ -0,0 +1,250 @@
+/// Summary statistics over a set of benchmark samples.
+use std::collections::HashMap;
+use std::time::Duration;
+
+use tinyjson::JsonValue;
+
+/// Samples whose modified z-score exceeds this value are counted as outliers.
+/// See: Iglewicz and Hoaglin, "How to Detect and Handle Outliers" (1993).
+const OUTLIER_THRESHOLD: f64 = 3.5;
+
+const HISTOGRAM_BUCKETS: usize = 16;
+const HISTOGRAM_BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Benchmark statistics for a single solution part. All durations are in nanoseconds.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Stats {
+    pub samples: u64,
+    pub warmup: u64,
+    pub min: f64,
+    pub median: f64,
+    pub p95: f64,
+    pub max: f64,
+    pub mean: f64,
+    pub stddev: f64,
+    pub outliers: u64,
+}
+
+impl Stats {
+    /// Computes statistics from a list of samples. Returns `None` if there are no samples.
+    pub fn from_samples(samples: &[Duration], warmup: u64) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let sorted = sorted_nanos(samples);
+        let count = sorted.len() as f64;
+
+        let mean = sorted.iter().sum::<f64>() / count;
+        let variance = sorted.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / count;
+        let median = percentile(&sorted, 0.5);
+
+        Some(Stats {
+            samples: sorted.len() as u64,
+            warmup,
+            min: sorted[0],
+            median,
+            p95: percentile(&sorted, 0.95),
+            max: sorted[sorted.len() - 1],
+            mean,
+            stddev: variance.sqrt(),
+            outliers: count_outliers(&sorted, median),
+        })
+    }
+
+    /// Formats the statistics as a single, human-readable line.
+    pub fn summary(&self) -> String {
+        format!(
+            "min {:.1?} | median {:.1?} | mean {:.1?} | p95 {:.1?} | max {:.1?} | σ {:.1?} | outliers {} | warmup {}",
+            nanos_to_duration(self.min),
+            nanos_to_duration(self.median),
+            nanos_to_duration(self.mean),
+            nanos_to_duration(self.p95),
+            nanos_to_duration(self.max),
+            nanos_to_duration(self.stddev),
+            self.outliers,
+            self.warmup
+        )
+    }
+}
+
+/// Renders a histogram of the samples as a line of block characters.
+/// The range spans from the fastest sample to the 95th percentile, slower samples are collected in the last bucket.
+pub fn histogram(samples: &[Duration]) -> String {
+    if samples.is_empty() {
+        return String::new();
+    }
+
+    let sorted = sorted_nanos(samples);
+    let low = sorted[0];
+    let high = percentile(&sorted, 0.95);
+    let width = (high - low).max(1.0) / HISTOGRAM_BUCKETS as f64;
+
+    let mut buckets = [0_usize; HISTOGRAM_BUCKETS];
+
+    for x in &sorted {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let index = ((x - low) / width) as usize;
+        buckets[index.min(HISTOGRAM_BUCKETS - 1)] += 1;
+    }
+
+    let tallest = buckets.iter().copied().max().unwrap_or(1);
+
+    let bars: String = buckets
+        .iter()
+        .map(|&count| {
+            if count == 0 {
+                ' '
+            } else {
+                HISTOGRAM_BARS[(count * (HISTOGRAM_BARS.len() - 1)) / tallest]
+            }
+        })
+        .collect();
+
+    format!(
+        "▕{bars}▏ {:.1?} … {:.1?}",
+        nanos_to_duration(low),
+        nanos_to_duration(high)
+    )
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn nanos_to_duration(nanos: f64) -> Duration {
+    Duration::from_nanos(nanos.max(0.0) as u64)
+}
+
+fn sorted_nanos(samples: &[Duration]) -> Vec<f64> {
+    let mut sorted: Vec<f64> = samples.iter().map(|x| x.as_nanos() as f64).collect();
+    sorted.sort_unstable_by(f64::total_cmp);
+    sorted
+}
+
+/// Nearest-rank percentile of a sorted, non-empty slice.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let rank = (p * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Counts samples that deviate from the median by more than [`OUTLIER_THRESHOLD`],
+/// using the median absolute deviation (MAD) as a robust measure of spread.
+fn count_outliers(sorted: &[f64], median: f64) -> u64 {
+    let mut deviations: Vec<f64> = sorted.iter().map(|x| (x - median).abs()).collect();
+    deviations.sort_unstable_by(f64::total_cmp);
+    let mad = percentile(&deviations, 0.5);
+
+    if mad == 0.0 {
+        return 0;
+    }
+
+    sorted
+        .iter()
+        .filter(|x| 0.6745 * (*x - median).abs() / mad > OUTLIER_THRESHOLD)
+        .count() as u64
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Stats> for JsonValue {
+    fn from(value: &Stats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("warmup".into(), JsonValue::Number(value.warmup as f64));
+        map.insert("min".into(), JsonValue::Number(value.min));
+        map.insert("median".into(), JsonValue::Number(value.median));
+        map.insert("p95".into(), JsonValue::Number(value.p95));
+        map.insert("max".into(), JsonValue::Number(value.max));
+        map.insert("mean".into(), JsonValue::Number(value.mean));
+        map.insert("stddev".into(), JsonValue::Number(value.stddev));
+        map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Stats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+
+        Ok(Stats {
+            samples: number("samples")? as u64,
+            warmup: number("warmup")? as u64,
+            min: number("min")?,
+            median: number("median")?,
+            p95: number("p95")?,
+            max: number("max")?,
+            mean: number("mean")?,
+            stddev: number("stddev")?,
+            outliers: number("outliers")? as u64,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{histogram, Stats};
+
+    fn durations(nanos: &[u64]) -> Vec<Duration> {
+        nanos.iter().map(|x| Duration::from_nanos(*x)).collect()
+    }
+
+    #[test]
+    fn handles_empty_samples() {
+        assert_eq!(Stats::from_samples(&[], 0), None);
+        assert_eq!(histogram(&[]), "");
+    }
+
+    #[test]
+    fn computes_order_statistics() {
+        let samples = durations(&[50, 10, 40, 20, 30]);
+        let stats = Stats::from_samples(&samples, 2).unwrap();
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.warmup, 2);
+        assert_eq!(stats.min, 10.0);
+        assert_eq!(stats.median, 30.0);
+        assert_eq!(stats.p95, 50.0);
+        assert_eq!(stats.max, 50.0);
+        assert_eq!(stats.mean, 30.0);
+        assert!((stats.stddev - 200_f64.sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn detects_outliers() {
+        let samples = durations(&[100, 101, 99, 100, 102, 98, 100, 5000]);
+        let stats = Stats::from_samples(&samples, 0).unwrap();
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.median, 100.0);
+    }
+
+    #[test]
+    fn ignores_outliers_for_constant_samples() {
+        let samples = durations(&[10, 10, 10, 10]);
+        let stats = Stats::from_samples(&samples, 0).unwrap();
+        assert_eq!(stats.outliers, 0);
+        assert_eq!(stats.stddev, 0.0);
+    }
+
+    #[test]
+    fn renders_histogram() {
+        let samples = durations(&[10, 10, 10, 20, 30]);
+        let rendered = histogram(&samples);
+        assert!(rendered.starts_with("▕█"));
+        assert!(rendered.ends_with("10.0ns … 30.0ns"));
+    }
+}
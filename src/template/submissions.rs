@@ -0,0 +1,430 @@
+/// Keeps a history of submitted answers, one file per year in `data/submissions/<year>.json`, and guards against pointless submissions.
+/// Every wrong answer counts towards the server's lockout, so answers that are known to be wrong are not submitted again.
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::{year_from_env, Day};
+
+static DATA_DIR: &str = "./data";
+
+/// The server's response to a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+}
+
+impl Verdict {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::Wrong => "wrong",
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low",
+        }
+    }
+
+    /// Reads the verdict from the text of a submission response.
+    /// Returns `None` for responses that do not judge the answer, e.g. when submitting too quickly.
+    pub fn from_response(response: &str) -> Option<Self> {
+        if response.contains("That's the right answer") {
+            Some(Verdict::Correct)
+        } else if response.contains("That's not the right answer") {
+            if response.contains("too high") {
+                Some(Verdict::TooHigh)
+            } else if response.contains("too low") {
+                Some(Verdict::TooLow)
+            } else {
+                Some(Verdict::Wrong)
+            }
+        } else {
+            None
+        }
+    }
+
+    pub fn describe(self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::Wrong => "wrong",
+            Verdict::TooHigh => "too high",
+            Verdict::TooLow => "too low",
+        }
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "wrong" => Ok(Verdict::Wrong),
+            "too_high" => Ok(Verdict::TooHigh),
+            "too_low" => Ok(Verdict::TooLow),
+            _ => Err(format!("Unknown verdict `{s}`.")),
+        }
+    }
+}
+
+/// A single submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    /// Seconds since the unix epoch.
+    pub time: u64,
+    pub verdict: Verdict,
+}
+
+/// Whether an answer should be submitted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Guard {
+    Allow,
+    /// The answer is known to be wrong or the part is solved already.
+    Refuse(String),
+    /// The answer looks suspicious and should only be submitted after confirmation.
+    Confirm(String),
+}
+
+/// All submitted answers, in the order they were submitted.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct History {
+    pub data: Vec<Submission>,
+}
+
+impl History {
+    /// Returns the path of the history of a year in a data directory, `<dir>/submissions/<year>.json`.
+    pub fn path_in(dir: &Path, year: u16) -> PathBuf {
+        dir.join("submissions").join(format!("{year}.json"))
+    }
+
+    /// Reads the history of the year in `AOC_YEAR` from `data`. If not present, returns an empty history.
+    pub fn read_from_file() -> Result<Self, String> {
+        History::read_in(Path::new(DATA_DIR), year_from_env()?)
+    }
+
+    /// Like [`History::read_from_file`], for a year in another data directory.
+    pub fn read_in(dir: &Path, year: u16) -> Result<Self, String> {
+        let path = History::path_in(dir, year);
+
+        match fs::read_to_string(&path) {
+            Ok(content) => {
+                History::try_from(content).map_err(|e| format!("{}: {e}", path.display()))
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(History::default()),
+            Err(e) => Err(format!("{}: {e}", path.display())),
+        }
+    }
+
+    /// Writes the history of the year in `AOC_YEAR` to `data`.
+    pub fn store_file(&self) -> Result<(), String> {
+        self.store_in(Path::new(DATA_DIR), year_from_env()?)
+    }
+
+    /// Like [`History::store_file`], for a year in another data directory.
+    pub fn store_in(&self, dir: &Path, year: u16) -> Result<(), String> {
+        let path = History::path_in(dir, year);
+        let json = JsonValue::from(self);
+
+        path.parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| fs::File::create(&path))
+            .and_then(|mut file| json.format_to(&mut file))
+            .map_err(|e| format!("{}: {e}", path.display()))
+    }
+
+    /// Records the verdict for an answer that was just submitted.
+    pub fn record(&mut self, day: Day, part: u8, answer: &str, verdict: Verdict) {
+        let time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+
+        self.data.push(Submission {
+            day,
+            part,
+            answer: answer.into(),
+            time,
+            verdict,
+        });
+    }
+
+    /// Returns when a part was solved by a correct submission, in seconds since the unix epoch.
+    pub fn solved_at(&self, day: Day, part: u8) -> Option<u64> {
+        self.data
+            .iter()
+            .find(|s| s.day == day && s.part == part && s.verdict == Verdict::Correct)
+            .map(|s| s.time)
+    }
+
+    /// Decides whether `answer` should be submitted for a part, based on earlier submissions and hints.
+    pub fn guard(&self, day: Day, part: u8, answer: &str) -> Guard {
+        let submissions: Vec<&Submission> = self
+            .data
+            .iter()
+            .filter(|s| s.day == day && s.part == part)
+            .collect();
+
+        if let Some(correct) = submissions.iter().find(|s| s.verdict == Verdict::Correct) {
+            return Guard::Refuse(format!(
+                "the part is already solved with {}.",
+                correct.answer
+            ));
+        }
+
+        if let Some(duplicate) = submissions.iter().find(|s| s.answer == answer) {
+            return Guard::Refuse(format!(
+                "{answer} was submitted before and was {}.",
+                duplicate.verdict.describe()
+            ));
+        }
+
+        if let Ok(value) = answer.trim().parse::<i128>() {
+            for submission in &submissions {
+                let Ok(bound) = submission.answer.trim().parse::<i128>() else {
+                    continue;
+                };
+
+                match submission.verdict {
+                    Verdict::TooHigh if value >= bound => {
+                        return Guard::Refuse(format!(
+                            "{answer} is not lower than {bound}, which was too high."
+                        ));
+                    }
+                    Verdict::TooLow if value <= bound => {
+                        return Guard::Refuse(format!(
+                            "{answer} is not higher than {bound}, which was too low."
+                        ));
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        if answer.trim().is_empty() {
+            Guard::Confirm("The answer is empty.".into())
+        } else if answer.contains('\n') {
+            Guard::Confirm("The answer spans multiple lines.".into())
+        } else if answer.trim() == "0" {
+            Guard::Confirm("The answer is 0.".into())
+        } else {
+            Guard::Allow
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&History> for JsonValue {
+    fn from(value: &History) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert("time".into(), JsonValue::Number(value.time as f64));
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(value.verdict.as_str().into()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for History {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(History {
+            data: json_data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected submission.day to be a Day struct.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected submission.{key} to be a number."))
+        };
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let verdict = json
+            .get("verdict")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.verdict to be a string.")?
+            .parse()?;
+
+        Ok(Submission {
+            day,
+            part: number("part")? as u8,
+            answer: answer.clone(),
+            time: number("time")? as u64,
+            verdict,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{Guard, History, Submission, Verdict};
+    use crate::day;
+
+    fn history(submissions: &[(u8, &str, Verdict)]) -> History {
+        History {
+            data: submissions
+                .iter()
+                .map(|(part, answer, verdict)| Submission {
+                    day: day!(7),
+                    part: *part,
+                    answer: (*answer).into(),
+                    time: 1_733_554_800,
+                    verdict: *verdict,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn reads_verdicts() {
+        assert_eq!(
+            Verdict::from_response("That's the right answer! You are one gold star closer."),
+            Some(Verdict::Correct)
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the right answer; your answer is too high."),
+            Some(Verdict::TooHigh)
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the right answer; your answer is too low."),
+            Some(Verdict::TooLow)
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the right answer. If you're stuck, ..."),
+            Some(Verdict::Wrong)
+        );
+        assert_eq!(
+            Verdict::from_response("You gave an answer too recently; you have to wait."),
+            None
+        );
+    }
+
+    #[test]
+    fn refuses_known_answers() {
+        let history = history(&[(1, "3749", Verdict::Correct), (2, "100", Verdict::Wrong)]);
+
+        assert_eq!(
+            history.guard(day!(7), 1, "3750"),
+            Guard::Refuse("the part is already solved with 3749.".into())
+        );
+        assert_eq!(
+            history.guard(day!(7), 2, "100"),
+            Guard::Refuse("100 was submitted before and was wrong.".into())
+        );
+        assert_eq!(history.guard(day!(7), 2, "101"), Guard::Allow);
+        assert_eq!(history.guard(day!(8), 1, "3750"), Guard::Allow);
+    }
+
+    #[test]
+    fn refuses_answers_outside_of_bounds() {
+        let history = history(&[
+            (2, "11400", Verdict::TooHigh),
+            (2, "11000", Verdict::TooLow),
+        ]);
+
+        assert_eq!(
+            history.guard(day!(7), 2, "11400"),
+            Guard::Refuse("11400 was submitted before and was too high.".into())
+        );
+        assert_eq!(
+            history.guard(day!(7), 2, "12000"),
+            Guard::Refuse("12000 is not lower than 11400, which was too high.".into())
+        );
+        assert_eq!(
+            history.guard(day!(7), 2, "10999"),
+            Guard::Refuse("10999 is not higher than 11000, which was too low.".into())
+        );
+        assert_eq!(history.guard(day!(7), 2, "11387"), Guard::Allow);
+        assert_eq!(history.guard(day!(7), 2, "ABC"), Guard::Allow);
+    }
+
+    #[test]
+    fn confirms_suspicious_answers() {
+        let history = History::default();
+
+        assert!(matches!(history.guard(day!(1), 1, "0"), Guard::Confirm(_)));
+        assert!(matches!(history.guard(day!(1), 1, ""), Guard::Confirm(_)));
+        assert!(matches!(
+            history.guard(day!(1), 1, "#..\n.#."),
+            Guard::Confirm(_)
+        ));
+    }
+
+    #[test]
+    fn roundtrips_history() {
+        let mut history = history(&[(1, "3749", Verdict::TooLow)]);
+        history.record(day!(12), 2, "line 1\nline 2", Verdict::Correct);
+
+        let json = JsonValue::from(&history).stringify().unwrap();
+        assert_eq!(History::try_from(json).unwrap(), history);
+    }
+}
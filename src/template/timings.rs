@@ -1,19 +1,133 @@
 use std::{collections::HashMap, fs, io::Error, str::FromStr};
 use tinyjson::JsonValue;
 
+use crate::template::bench::BenchSettings;
+use crate::template::cpu::CpuTime;
+use crate::template::memory::Memory;
+use crate::template::report::{Record, Stage, Status};
+use crate::template::stats::{nanos_to_duration, Stats};
 use crate::template::Day;
 
 static TIMINGS_FILE_PATH: &str = "./data/timings.json";
 
+/// Represents the benchmark results of a single stage of a day.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StageTiming {
+    /// The benched duration, formatted with `{:.1?}`.
+    pub time: Option<String>,
+    pub stats: Option<Stats>,
+    pub memory: Option<Memory>,
+    pub cpu: Option<CpuTime>,
+    pub status: Option<Status>,
+}
+
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    pub parse: StageTiming,
+    pub part_1: StageTiming,
+    pub part_2: StageTiming,
+    /// The settings the day was benched with.
+    pub bench: Option<BenchSettings>,
+    /// Number of threads in the rayon thread pool the day ran on.
+    pub threads: Option<u64>,
     pub total_nanos: f64,
 }
 
+impl Timing {
+    /// Creates an empty timing for a day that has not been timed yet.
+    pub fn new(day: Day) -> Self {
+        Timing {
+            day,
+            parse: StageTiming::default(),
+            part_1: StageTiming::default(),
+            part_2: StageTiming::default(),
+            bench: None,
+            threads: None,
+            total_nanos: 0_f64,
+        }
+    }
+
+    /// Collect the timings for a day from the records reported by its solution binary.
+    /// Parts without an answer are not timed, but their status is kept.
+    pub fn from_records(day: Day, records: &[Record]) -> Self {
+        let mut timing = Timing {
+            threads: records.iter().find_map(|r| r.threads),
+            ..Timing::new(day)
+        };
+
+        for record in records {
+            let Some(stage) = timing.stage_mut(record.stage) else {
+                continue;
+            };
+
+            if record.status != Status::Ok {
+                stage.status = Some(record.status);
+                continue;
+            }
+
+            *stage = StageTiming {
+                time: Some(format!("{:.1?}", nanos_to_duration(record.nanos))),
+                stats: record.stats.clone(),
+                memory: record.memory.clone(),
+                cpu: record.cpu.clone(),
+                status: Some(record.status),
+            };
+            timing.total_nanos += record.nanos;
+        }
+
+        timing
+    }
+
+    /// Returns the timing of a stage, `None` for parts other than 1 and 2.
+    pub fn stage(&self, stage: Stage) -> Option<&StageTiming> {
+        match stage {
+            Stage::Parse => Some(&self.parse),
+            Stage::Part(1) => Some(&self.part_1),
+            Stage::Part(2) => Some(&self.part_2),
+            Stage::Part(_) => None,
+        }
+    }
+
+    fn stage_mut(&mut self, stage: Stage) -> Option<&mut StageTiming> {
+        match stage {
+            Stage::Parse => Some(&mut self.parse),
+            Stage::Part(1) => Some(&mut self.part_1),
+            Stage::Part(2) => Some(&mut self.part_2),
+            Stage::Part(_) => None,
+        }
+    }
+
+    /// Returns the benched duration of a stage in nanoseconds, if it was timed.
+    /// Prefers the median from the stats, timings stored by older versions only contain the formatted duration.
+    pub fn nanos(&self, stage: Stage) -> Option<f64> {
+        let stage = self.stage(stage)?;
+
+        stage
+            .stats
+            .as_ref()
+            .map(|stats| stats.median)
+            .or_else(|| stage.time.as_deref().and_then(parse_duration))
+    }
+}
+
+/// Parses a duration formatted with `{:.1?}`, e.g. `74.1µs`, into nanoseconds.
+fn parse_duration(s: &str) -> Option<f64> {
+    let split = s.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+    let (value, unit) = s.split_at(split);
+
+    let factor = match unit {
+        "ns" => 1.0,
+        "µs" => 1_000.0,
+        "ms" => 1_000_000.0,
+        "s" => 1_000_000_000.0,
+        _ => return None,
+    };
+
+    value.parse::<f64>().ok().map(|x| x * factor)
+}
+
 /// Represents benchmark times for a set of days.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
@@ -58,7 +172,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -70,7 +184,7 @@ impl Timings {
     pub fn is_day_complete(&self, day: Day) -> bool {
         self.data
             .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+            .any(|t| t.day == day && t.part_1.time.is_some() && t.part_2.time.is_some())
     }
 }
 
@@ -121,21 +235,64 @@ impl From<&Timing> for JsonValue {
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
+        for (key, stage) in [
+            ("parse", &value.parse),
+            ("part_1", &value.part_1),
+            ("part_2", &value.part_2),
+        ] {
+            map.insert(
+                key.into(),
+                match &stage.time {
+                    Some(x) => JsonValue::String(x.clone()),
+                    None => JsonValue::Null,
+                },
+            );
+
+            map.insert(
+                format!("{key}_stats"),
+                match &stage.stats {
+                    Some(x) => JsonValue::from(x),
+                    None => JsonValue::Null,
+                },
+            );
+
+            map.insert(
+                format!("{key}_memory"),
+                match &stage.memory {
+                    Some(x) => JsonValue::from(x),
+                    None => JsonValue::Null,
+                },
+            );
+
+            map.insert(
+                format!("{key}_cpu"),
+                match &stage.cpu {
+                    Some(x) => JsonValue::from(x),
+                    None => JsonValue::Null,
+                },
+            );
+
+            map.insert(
+                format!("{key}_status"),
+                match stage.status {
+                    Some(x) => JsonValue::String(x.as_str().into()),
+                    None => JsonValue::Null,
+                },
+            );
+        }
 
         map.insert(
-            "part_1".into(),
-            match part_1 {
-                Some(x) => x,
+            "bench".into(),
+            match &value.bench {
+                Some(x) => JsonValue::from(x),
                 None => JsonValue::Null,
             },
         );
 
         map.insert(
-            "part_2".into(),
-            match part_2 {
-                Some(x) => x,
+            "threads".into(),
+            match value.threads {
+                Some(x) => JsonValue::Number(x as f64),
                 None => JsonValue::Null,
             },
         );
@@ -147,6 +304,7 @@ impl From<&Timing> for JsonValue {
 impl TryFrom<&JsonValue> for Timing {
     type Error = String;
 
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
     fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
         let json = value
             .get::<HashMap<String, JsonValue>>()
@@ -173,43 +331,106 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // parse timings and stats were added in a later version of the template, treat them as optional.
+        let parse = json.get("parse").and_then(|v| v.get::<String>()).cloned();
+        let stage = |key: &str, time: Option<String>| -> Result<StageTiming, String> {
+            Ok(StageTiming {
+                time,
+                stats: optional(json, "timing", &format!("{key}_stats"), |v| {
+                    Stats::try_from(v)
+                })?,
+                memory: optional(json, "timing", &format!("{key}_memory"), |v| {
+                    Memory::try_from(v)
+                })?,
+                cpu: optional(json, "timing", &format!("{key}_cpu"), |v| {
+                    CpuTime::try_from(v)
+                })?,
+                status: optional(json, "timing", &format!("{key}_status"), status_from_json)?,
+            })
+        };
+        let bench = match json.get("bench") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(BenchSettings::try_from(v).map_err(|e| format!("timing.bench: {e}"))?),
+        };
+        let threads = match json.get("threads") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(
+                v.get::<f64>()
+                    .copied()
+                    .ok_or("Expected timing.threads to be null or a number.")?
+                    as u64,
+            ),
+        };
+
         Ok(Timing {
             day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
+            parse: stage("parse", parse)?,
+            part_1: stage("part_1", part_1.cloned())?,
+            part_2: stage("part_2", part_2.cloned())?,
+            bench,
+            threads,
             total_nanos,
         })
     }
 }
 
+/// Reads an optional field of a JSON `object` with `parse`, `None` if it is missing or null.
+pub(crate) fn optional<T>(
+    json: &HashMap<String, JsonValue>,
+    object: &str,
+    key: &str,
+    parse: impl FnOnce(&JsonValue) -> Result<T, String>,
+) -> Result<Option<T>, String> {
+    match json.get(key) {
+        None => Ok(None),
+        Some(v) if v.is_null() => Ok(None),
+        Some(v) => parse(v)
+            .map(Some)
+            .map_err(|e| format!("{object}.{key}: {e}")),
+    }
+}
+
+fn status_from_json(value: &JsonValue) -> Result<Status, String> {
+    value.get::<String>().ok_or("Expected a string.")?.parse()
+}
+
 /* -------------------------------------------------------------------------- */
 
 #[cfg(feature = "test_lib")]
 mod tests {
     use crate::day;
 
-    use super::{Timing, Timings};
+    use super::{StageTiming, Timing, Timings};
+
+    /// A stage that was timed at `time`.
+    fn timed(time: &str) -> StageTiming {
+        StageTiming {
+            time: Some(time.into()),
+            ..StageTiming::default()
+        }
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    part_1: timed("10ms"),
+                    part_2: timed("20ms"),
                     total_nanos: 3e+10,
+                    ..Timing::new(day!(1))
                 },
                 Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    part_1: timed("30ms"),
+                    part_2: timed("40ms"),
                     total_nanos: 7e+10,
+                    ..Timing::new(day!(2))
                 },
                 Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: None,
+                    part_1: timed("40ms"),
                     total_nanos: 4e+10,
+                    ..Timing::new(day!(4))
                 },
             ],
         }
@@ -225,11 +446,23 @@ mod tests {
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
-            assert_eq!(timing.part_2, None);
+            assert_eq!(timing.part_1.time, Some("1ms".to_string()));
+            assert_eq!(timing.part_2.time, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_json_timings_with_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "part_1_stats": { "samples": 10, "warmup": 1, "min": 900000, "median": 1000000, "mean": 1100000, "p95": 1500000, "max": 2000000, "stddev": 300000, "outliers": 1 }, "part_2_stats": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let stats = timing.part_1.stats.as_ref().unwrap();
+            assert_eq!(stats.samples, 10);
+            assert_eq!(stats.median, 1_000_000_f64);
+            assert_eq!(stats.outliers, 1);
+            assert_eq!(timing.part_2.stats, None);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -254,9 +487,57 @@ mod tests {
 
     mod serialization {
         use super::get_mock_timings;
+        use crate::template::{
+            bench::BenchSettings, cpu::CpuTime, memory::Memory, report::Status, stats::Stats,
+            timings::Timings,
+        };
         use std::collections::HashMap;
         use tinyjson::JsonValue;
 
+        #[test]
+        fn roundtrips_stats() {
+            let mut timings = get_mock_timings();
+            timings.data[0].part_1.stats = Some(Stats {
+                samples: 100,
+                warmup: 10,
+                min: 1.0,
+                median: 2.0,
+                p95: 3.0,
+                max: 4.0,
+                mean: 2.5,
+                stddev: 0.5,
+                outliers: 3,
+            });
+            timings.data[2].part_2.status = Some(Status::TimedOut);
+            timings.data[2].threads = Some(16);
+            timings.data[2].bench = Some(BenchSettings {
+                max_samples: 100,
+                warmup: Some(5),
+                ..BenchSettings::default()
+            });
+            timings.data[1].part_2.memory = Some(Memory {
+                peak_bytes: 1024,
+                allocations: 3,
+                allocated_bytes: 4096,
+            });
+            timings.data[1].part_1.cpu = Some(CpuTime {
+                nanos: 2_000_000.0,
+                ratio: 7.5,
+            });
+            let json = JsonValue::from(timings.clone()).stringify().unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data[0].part_1.stats, timings.data[0].part_1.stats);
+            assert_eq!(parsed.data[0].part_2.stats, None);
+            assert_eq!(parsed.data[1].part_2.memory, timings.data[1].part_2.memory);
+            assert_eq!(parsed.data[1].part_1.cpu, timings.data[1].part_1.cpu);
+            assert_eq!(parsed.data[1].part_2.cpu, None);
+            assert_eq!(parsed.data[2].part_2.status, Some(Status::TimedOut));
+            assert_eq!(parsed.data[2].part_1.status, None);
+            assert_eq!(parsed.data[2].bench, timings.data[2].bench);
+            assert_eq!(parsed.data[2].threads, Some(16));
+            assert_eq!(parsed.data[1].bench, None);
+        }
+
         #[test]
         fn serializes_timings() {
             let timings = get_mock_timings();
@@ -275,52 +556,168 @@ mod tests {
         }
     }
 
+    mod from_records {
+        use crate::{
+            day,
+            template::{
+                cpu::CpuTime,
+                report::{Record, Stage, Status},
+                stats::Stats,
+                timings::Timing,
+            },
+        };
+
+        fn record(stage: Stage, nanos: f64, status: Status) -> Record {
+            Record {
+                stage,
+                answer: Some("42".into()),
+                nanos,
+                samples: 100,
+                status,
+                message: None,
+                stats: None,
+                cpu: None,
+                memory: None,
+                threads: Some(4),
+            }
+        }
+
+        #[test]
+        fn reads_stage_nanos() {
+            let mut timing = Timing::from_records(
+                day!(1),
+                &[
+                    record(Stage::Part(1), 74.13, Status::Ok),
+                    record(Stage::Part(2), 2_500_000.0, Status::Ok),
+                ],
+            );
+            assert_eq!(timing.nanos(Stage::Part(1)), Some(74.0));
+            assert_eq!(timing.nanos(Stage::Part(2)), Some(2_500_000.0));
+            assert_eq!(timing.nanos(Stage::Parse), None);
+
+            timing.part_1.stats = Some(Stats {
+                median: 74.13,
+                ..Stats::default()
+            });
+            assert_eq!(timing.nanos(Stage::Part(1)), Some(74.13));
+
+            timing.part_2.time = Some("1.5s".into());
+            assert_eq!(timing.nanos(Stage::Part(2)), Some(1_500_000_000.0));
+        }
+
+        #[test]
+        fn collects_execution_times() {
+            let res = Timing::from_records(
+                day!(1),
+                &[
+                    record(Stage::Part(1), 74.13, Status::Ok),
+                    record(Stage::Part(2), 74_130_000.0, Status::Ok),
+                ],
+            );
+            assert_eq!(res.total_nanos, 74_130_074.13_f64);
+            assert_eq!(res.part_1.time.unwrap(), "74.0ns");
+            assert_eq!(res.part_2.time.unwrap(), "74.1ms");
+            assert_eq!(res.parse.time, None);
+            assert_eq!(res.threads, Some(4));
+        }
+
+        #[test]
+        fn collects_parse_stage_and_stats() {
+            let mut parse = record(Stage::Parse, 1_500_000.0, Status::Ok);
+            parse.stats = Some(Stats {
+                samples: 100,
+                ..Stats::default()
+            });
+            parse.cpu = Some(CpuTime {
+                nanos: 3_000_000.0,
+                ratio: 2.0,
+            });
+
+            let res = Timing::from_records(
+                day!(1),
+                &[parse, record(Stage::Part(1), 2_000_000_000.0, Status::Ok)],
+            );
+            assert_eq!(res.total_nanos, 2_001_500_000_f64);
+            assert_eq!(res.parse.time.unwrap(), "1.5ms");
+            assert_eq!(res.parse.stats.unwrap().samples, 100);
+            assert_eq!(res.parse.cpu.unwrap().ratio, 2.0);
+            assert_eq!(res.part_1.time.unwrap(), "2.0s");
+            assert_eq!(res.part_1.stats, None);
+        }
+
+        #[test]
+        fn handles_missing_parts() {
+            let res = Timing::from_records(
+                day!(1),
+                &[
+                    record(Stage::Part(1), 10.0, Status::NoAnswer),
+                    record(Stage::Part(2), 10.0, Status::NoAnswer),
+                ],
+            );
+            assert_eq!(res.total_nanos, 0_f64);
+            assert!(res.part_1.time.is_none());
+            assert!(res.part_2.time.is_none());
+            assert_eq!(res.part_1.status, Some(Status::NoAnswer));
+        }
+
+        #[test]
+        fn keeps_timed_out_parts() {
+            let res = Timing::from_records(
+                day!(1),
+                &[
+                    record(Stage::Part(1), 20.0, Status::Ok),
+                    record(Stage::Part(2), 10_000_000_000.0, Status::TimedOut),
+                ],
+            );
+            assert_eq!(res.total_nanos, 20_f64);
+            assert!(res.part_2.time.is_none());
+            assert_eq!(res.part_1.status, Some(Status::Ok));
+            assert_eq!(res.part_2.status, Some(Status::TimedOut));
+        }
+    }
+
     mod is_day_complete {
         use crate::{
             day,
             template::timings::{Timing, Timings},
         };
 
+        use super::timed;
+
         #[test]
         fn handles_completed_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
+                    part_1: timed("1ms"),
+                    part_2: timed("2ms"),
                     total_nanos: 3_000_000_000_f64,
+                    ..Timing::new(day!(1))
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
         fn handles_partial_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: None,
+                    part_1: timed("1ms"),
                     total_nanos: 1_000_000_000_f64,
+                    ..Timing::new(day!(1))
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
         fn handles_uncompleted_days() {
             let timings = Timings {
-                data: vec![Timing {
-                    day: day!(1),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0.0,
-                }],
+                data: vec![Timing::new(day!(1))],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
@@ -336,12 +733,7 @@ mod tests {
         fn handles_disjunct_timings() {
             let timings = get_mock_timings();
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(3),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![Timing::new(day!(3))],
             };
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 4);
@@ -356,12 +748,7 @@ mod tests {
             let timings = get_mock_timings();
 
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(2),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![Timing::new(day!(2))],
             };
             let merged = timings.merge(&other);
 